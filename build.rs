@@ -0,0 +1,54 @@
+/*
+ * Generates one test per example case in `src/examples/NN/`, see `aoc::example_tests!`.
+ */
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/examples");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for day in 1..=25 {
+        let mut names: Vec<String> = match fs::read_dir(format!("src/examples/{:02}", day)) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|p| Some(p.file_stem()?.to_str()?.to_owned()))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        names.sort();
+
+        let tests: String = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "#[test]\nfn {}() {{\n    run_example({:?});\n}}\n",
+                    test_name(name),
+                    name
+                )
+            })
+            .collect();
+
+        let path = Path::new(&out_dir).join(format!("examples_{}.rs", day));
+        fs::write(path, tests).unwrap();
+    }
+}
+
+fn test_name(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("case_{}", ident)
+    } else {
+        ident
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+
+use itertools::iproduct;
+use num::{traits::SaturatingAdd, Bounded, One, Zero};
+
+/// Shortest distances between all pairs of nodes `0..len`.
+///
+/// Graphs are given as adjacency lists indexed by node. Unreachable pairs have the distance
+/// `T::max_value()`, so pick a type that is wide enough for the longest path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMatrix<T> {
+    len: usize,
+    distances: Vec<T>,
+}
+
+impl<T> DistanceMatrix<T>
+where
+    T: Copy + Ord + Zero + One + Bounded + SaturatingAdd,
+{
+    /// Creates a matrix where every node only reaches itself.
+    pub fn new(len: usize) -> Self {
+        let mut matrix = DistanceMatrix {
+            len,
+            distances: vec![T::max_value(); len * len],
+        };
+        for i in 0..len {
+            matrix.set(i, i, T::zero());
+        }
+        matrix
+    }
+
+    /// All-pairs distances of an unweighted graph using Floyd–Warshall.
+    pub fn floyd_warshall(adjacency: &[Vec<usize>]) -> Self {
+        let mut matrix = DistanceMatrix::new(adjacency.len());
+        for (i, neighbors) in adjacency.iter().enumerate() {
+            for &j in neighbors {
+                if i != j {
+                    matrix.set(i, j, T::one());
+                }
+            }
+        }
+        matrix.relax_all();
+        matrix
+    }
+
+    /// All-pairs distances of a weighted graph using Floyd–Warshall.
+    pub fn floyd_warshall_weighted(adjacency: &[Vec<(usize, T)>]) -> Self {
+        let mut matrix = DistanceMatrix::new(adjacency.len());
+        for (i, neighbors) in adjacency.iter().enumerate() {
+            for &(j, cost) in neighbors {
+                if cost < matrix.get(i, j) {
+                    matrix.set(i, j, cost);
+                }
+            }
+        }
+        matrix.relax_all();
+        matrix
+    }
+
+    /// All-pairs distances of an unweighted graph using a BFS from every node.
+    ///
+    /// Faster than [`DistanceMatrix::floyd_warshall`] for sparse graphs.
+    pub fn repeated_bfs(adjacency: &[Vec<usize>]) -> Self {
+        let mut matrix = DistanceMatrix::new(adjacency.len());
+        let mut queue = VecDeque::new();
+
+        for start in 0..adjacency.len() {
+            queue.push_back(start);
+            while let Some(current) = queue.pop_front() {
+                let distance = matrix.get(start, current) + T::one();
+                for &neighbor in &adjacency[current] {
+                    if matrix.get(start, neighbor) == T::max_value() {
+                        matrix.set(start, neighbor, distance);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+        matrix
+    }
+
+    fn relax_all(&mut self) {
+        let len = self.len;
+        for (k, i, j) in iproduct!(0..len, 0..len, 0..len) {
+            let sum = self.distances[i * len + k].saturating_add(&self.distances[k * len + j]);
+            if self.distances[i * len + j] > sum {
+                self.distances[i * len + j] = sum;
+            }
+        }
+    }
+
+    /// Restricts the matrix to the given nodes, e.g. to drop the ones that only connect others.
+    ///
+    /// Node `nodes[i]` of this matrix becomes node `i` of the returned one.
+    pub fn compress(&self, nodes: &[usize]) -> Self {
+        let mut matrix = DistanceMatrix::new(nodes.len());
+        for (i, &from) in nodes.iter().enumerate() {
+            for (j, &to) in nodes.iter().enumerate() {
+                matrix.set(i, j, self.get(from, to));
+            }
+        }
+        matrix
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, from: usize, to: usize) -> T {
+        self.distances[from * self.len + to]
+    }
+
+    /// The distance between two nodes, or `None` if `to` cannot be reached from `from`.
+    pub fn distance(&self, from: usize, to: usize) -> Option<T> {
+        let d = self.get(from, to);
+        (d != T::max_value()).then_some(d)
+    }
+
+    #[inline]
+    pub fn set(&mut self, from: usize, to: usize, distance: T) {
+        self.distances[from * self.len + to] = distance;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 plus a shortcut 0 -> 2, and 4 unreachable.
+    fn adjacency() -> Vec<Vec<usize>> {
+        vec![vec![1, 2], vec![2], vec![3], vec![], vec![0]]
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let fw = DistanceMatrix::<u8>::floyd_warshall(&adjacency());
+        let bfs = DistanceMatrix::<u8>::repeated_bfs(&adjacency());
+        assert_eq!(fw, bfs);
+
+        assert_eq!(fw.distance(0, 3), Some(2));
+        assert_eq!(fw.distance(4, 3), Some(3));
+        assert_eq!(fw.distance(3, 0), None);
+        assert_eq!(fw.distance(2, 2), Some(0));
+    }
+
+    #[test]
+    fn test_weighted_and_compressed() {
+        let adjacency = vec![vec![(1, 1), (2, 5)], vec![(2, 1)], vec![(0, 1)]];
+        let matrix = DistanceMatrix::<u32>::floyd_warshall_weighted(&adjacency);
+        assert_eq!(matrix.distance(0, 2), Some(2));
+
+        let compressed = matrix.compress(&[2, 0]);
+        assert_eq!(compressed.len(), 2);
+        assert_eq!(compressed.distance(1, 0), Some(2));
+        assert_eq!(compressed.distance(0, 1), Some(1));
+    }
+}
@@ -0,0 +1,31 @@
+/// Returns the correct answer to `part` of `day` for the real input, if it has been recorded.
+///
+/// Answers live in `src/answers/NN.txt`, with the answer to part one on the first line and
+/// the answer to part two on the second one. An empty line means the answer is not recorded.
+pub fn expected_answer(day: u8, part: u8) -> Option<String> {
+    let answers = crate::try_read_file("answers", day).ok()?;
+    parse_answer(&answers, part)
+}
+
+fn parse_answer(answers: &str, part: u8) -> Option<String> {
+    let answer = answers.lines().nth(part.checked_sub(1)? as usize)?.trim();
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("24000\n45000\n", 1), Some("24000".to_owned()));
+        assert_eq!(parse_answer("24000\n45000\n", 2), Some("45000".to_owned()));
+        assert_eq!(parse_answer("\n45000", 1), None);
+        assert_eq!(parse_answer("24000", 2), None);
+        assert_eq!(parse_answer("24000", 0), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::Context;
+
+use crate::record::Record;
+
+/// Default regression threshold in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Loads the records of a baseline saved with [`save_baseline`].
+pub fn load_baseline(path: &Path) -> anyhow::Result<Vec<Record>> {
+    let s = fs::read_to_string(path)
+        .with_context(|| format!("could not read baseline '{}'", path.display()))?;
+    serde_json::from_str(&s).with_context(|| format!("invalid baseline '{}'", path.display()))
+}
+
+/// Saves the solved records as a baseline.
+///
+/// If the file already exists, records for the days and parts not included in `records` are
+/// kept, so that running a subset of days only updates their timings.
+pub fn save_baseline(path: &Path, records: &[Record]) -> anyhow::Result<()> {
+    let mut baseline = if path.exists() {
+        load_baseline(path)?
+    } else {
+        Vec::new()
+    };
+
+    for record in records.iter().filter(|r| r.answer.is_some()) {
+        baseline.retain(|b| (b.day, b.part) != (record.day, record.part));
+        baseline.push(record.clone());
+    }
+    baseline.sort_by_key(|r| (r.day, r.part));
+
+    fs::write(path, serde_json::to_string_pretty(&baseline)? + "\n")
+        .with_context(|| format!("could not write baseline '{}'", path.display()))
+}
+
+/// Timing of a part in the baseline and in the current run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Comparison {
+    pub fn baseline(&self) -> Duration {
+        Duration::from_nanos(self.baseline_ns)
+    }
+
+    pub fn current(&self) -> Duration {
+        Duration::from_nanos(self.current_ns)
+    }
+
+    /// Change relative to the baseline in percent. Positive values mean the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_ns == 0 {
+            return 0.0;
+        }
+        (self.current_ns as f64 - self.baseline_ns as f64) / self.baseline_ns as f64 * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Pairs each solved part of `current` with the same part in `baseline`.
+///
+/// Parts missing from either side are left out.
+pub fn compare(baseline: &[Record], current: &[Record]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter(|r| r.answer.is_some())
+        .filter_map(|r| {
+            baseline
+                .iter()
+                .find(|b| b.day == r.day && b.part == r.part && b.answer.is_some())
+                .map(|b| Comparison {
+                    day: r.day,
+                    part: r.part,
+                    baseline_ns: b.elapsed_ns,
+                    current_ns: r.elapsed_ns,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, part: u8, elapsed_ns: u64) -> Record {
+        Record {
+            day,
+            part,
+            answer: Some("42".to_owned()),
+            error: None,
+            elapsed_ns,
+            stats: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = [record(1, 1, 1000), record(1, 2, 2000), record(2, 1, 500)];
+        let current = [record(1, 1, 1200), record(1, 2, 1000), record(3, 1, 10)];
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 2);
+
+        assert_eq!(comparisons[0].change_percent(), 20.0);
+        assert!(comparisons[0].is_regression(DEFAULT_THRESHOLD));
+        assert!(!comparisons[0].is_regression(25.0));
+
+        assert_eq!(comparisons[1].change_percent(), -50.0);
+        assert!(!comparisons[1].is_regression(DEFAULT_THRESHOLD));
+    }
+
+    #[test]
+    fn test_save_merges_with_existing_baseline() {
+        let path = std::env::temp_dir().join("aoc_baseline_test.json");
+        let _ = fs::remove_file(&path);
+
+        save_baseline(&path, &[record(1, 1, 1000), record(2, 1, 500)]).unwrap();
+        save_baseline(&path, &[record(2, 1, 400), record(1, 2, 300)]).unwrap();
+
+        let baseline = load_baseline(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            baseline,
+            vec![record(1, 1, 1000), record(1, 2, 300), record(2, 1, 400)]
+        );
+    }
+}
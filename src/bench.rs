@@ -0,0 +1,178 @@
+use std::{env, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the environment variable enabling bench mode. A numeric value sets the sample count.
+pub const BENCH_ENV: &str = "AOC_BENCH";
+
+/// How a part is run in bench mode.
+///
+/// Each part is first run `warmup` times and then sampled up to `samples` times. Both phases
+/// stop early once `budget` has been spent, but at least one sample is always taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: u32,
+    pub samples: u32,
+    pub budget: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            samples: 100,
+            budget: Duration::from_secs(5),
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads the bench configuration from the command line and [`BENCH_ENV`].
+    pub fn from_env() -> Option<BenchConfig> {
+        let mut args = pico_args::Arguments::from_env();
+        BenchConfig::from_args(&mut args).unwrap_or_else(|e| {
+            eprintln!("invalid bench arguments: {}", e);
+            None
+        })
+    }
+
+    /// Consumes `--bench`, `--bench-samples <n>` and `--bench-budget <seconds>` from `args`.
+    pub fn from_args(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<BenchConfig>, pico_args::Error> {
+        let flag = args.contains("--bench");
+        let samples: Option<u32> = args.opt_value_from_str("--bench-samples")?;
+        let budget = args.opt_value_from_fn("--bench-budget", parse_budget)?;
+        let env = env::var(BENCH_ENV).ok();
+
+        if !flag && samples.is_none() && budget.is_none() && env.is_none() {
+            return Ok(None);
+        }
+
+        let mut config = BenchConfig::default();
+        if let Some(samples) = samples.or_else(|| env.and_then(|v| v.parse().ok())) {
+            config.samples = samples.max(1);
+        }
+        if let Some(budget) = budget {
+            config.budget = budget;
+        }
+        Ok(Some(config))
+    }
+}
+
+/// Parses a budget in seconds, rejecting negative, NaN and too large values.
+fn parse_budget(s: &str) -> anyhow::Result<Duration> {
+    let seconds: f64 = s.parse()?;
+    Ok(Duration::try_from_secs_f64(seconds)?)
+}
+
+/// Summary of the samples collected for a single part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub samples: u32,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &mut [u64]) -> BenchStats {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let mean = samples.iter().map(|&s| s as f64).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples
+                .iter()
+                .map(|&s| (s as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        BenchStats {
+            samples: n as u32,
+            min_ns: samples[0],
+            median_ns: median,
+            mean_ns: mean.round() as u64,
+            stddev_ns: variance.sqrt().round() as u64,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.mean_ns)
+    }
+
+    pub fn stddev(&self) -> Duration {
+        Duration::from_nanos(self.stddev_ns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_odd_samples() {
+        let stats = BenchStats::from_samples(&mut [30, 10, 20]);
+        assert_eq!(
+            stats,
+            BenchStats {
+                samples: 3,
+                min_ns: 10,
+                median_ns: 20,
+                mean_ns: 20,
+                stddev_ns: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_from_even_samples() {
+        let stats = BenchStats::from_samples(&mut [40, 10, 20, 30]);
+        assert_eq!(stats.min_ns, 10);
+        assert_eq!(stats.median_ns, 25);
+        assert_eq!(stats.mean_ns, 25);
+        assert_eq!(stats.stddev_ns, 13);
+    }
+
+    #[test]
+    fn test_stats_from_single_sample() {
+        let stats = BenchStats::from_samples(&mut [42]);
+        assert_eq!(stats.median_ns, 42);
+        assert_eq!(stats.stddev_ns, 0);
+    }
+
+    #[test]
+    fn test_invalid_budget() {
+        let args = |budget: &str| {
+            let mut args =
+                pico_args::Arguments::from_vec(vec!["--bench-budget".into(), budget.into()]);
+            BenchConfig::from_args(&mut args)
+        };
+        assert_eq!(
+            args("0.5").unwrap().unwrap().budget,
+            Duration::from_millis(500)
+        );
+        for budget in ["-1", "NaN", "1e30", "soon"] {
+            assert!(args(budget).is_err(), "{}", budget);
+        }
+    }
+}
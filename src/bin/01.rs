@@ -36,9 +36,9 @@ fn parse_elf_totals(input: &str) -> impl Iterator<Item = u32> + '_ {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 1);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(1);
+    aoc::solve!(1, 1, part_one, input);
+    aoc::solve!(1, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -1,29 +1,31 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    let result = input
-        .lines()
-        .map(|s| s.split_once(' ').unwrap())
-        .map(|r| {
-            let op = Shape::from_code(r.0);
-            let me = Shape::from_code(r.1);
-
-            me.score() + me.result(op).score()
-        })
-        .sum();
-    Some(result)
+use anyhow::{anyhow, bail};
+use aoc::helpers::try_lines;
+
+pub fn part_one(input: &str) -> anyhow::Result<u32> {
+    try_lines(input, |s| {
+        let r = split_round(s)?;
+        let op = Shape::from_code(r.0)?;
+        let me = Shape::from_code(r.1)?;
+
+        Ok(me.score() + me.result(op).score())
+    })
+    .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let result = input
-        .lines()
-        .map(|s| s.split_once(' ').unwrap())
-        .map(|r| {
-            let op = Shape::from_code(r.0);
-            let result = Result::from_code(r.1);
+pub fn part_two(input: &str) -> anyhow::Result<u32> {
+    try_lines(input, |s| {
+        let r = split_round(s)?;
+        let op = Shape::from_code(r.0)?;
+        let result = Result::from_code(r.1)?;
 
-            op.shape_for_result(result).score() + result.score()
-        })
-        .sum();
-    Some(result)
+        Ok(op.shape_for_result(result).score() + result.score())
+    })
+    .sum()
+}
+
+fn split_round(s: &str) -> anyhow::Result<(&str, &str)> {
+    s.split_once(' ')
+        .ok_or_else(|| anyhow!("expected two codes, got '{}'", s))
 }
 
 #[derive(Clone, Copy)]
@@ -41,13 +43,13 @@ enum Result {
 }
 
 impl Shape {
-    fn from_code(str: &str) -> Shape {
-        match str {
+    fn from_code(str: &str) -> anyhow::Result<Shape> {
+        Ok(match str {
             "A" | "X" => Shape::Rock,
             "B" | "Y" => Shape::Paper,
             "C" | "Z" => Shape::Scissors,
-            _ => panic!("invalid code '{}'", str),
-        }
+            _ => bail!("invalid shape code '{}'", str),
+        })
     }
 
     fn score(self) -> u32 {
@@ -74,13 +76,13 @@ impl Shape {
 }
 
 impl Result {
-    fn from_code(str: &str) -> Self {
-        match str {
+    fn from_code(str: &str) -> anyhow::Result<Self> {
+        Ok(match str {
             "X" => Self::Lose,
             "Y" => Self::Draw,
             "Z" => Self::Win,
-            _ => panic!("invalid code '{}'", str),
-        }
+            _ => bail!("invalid result code '{}'", str),
+        })
     }
 
     fn score(self) -> u32 {
@@ -89,9 +91,9 @@ impl Result {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 2);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(2);
+    aoc::solve!(2, 1, part_one, input);
+    aoc::solve!(2, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -101,12 +103,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(15));
+        assert_eq!(part_one(&input).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_invalid_code() {
+        let err = part_one("A Y\nB Q\n").unwrap_err();
+        assert_eq!(format!("{:#}", err), "line 2: invalid shape code 'Q'");
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(12));
+        assert_eq!(part_two(&input).unwrap(), 12);
     }
 }
@@ -114,9 +114,9 @@ impl From<char> for Item {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 3);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(3);
+    aoc::solve!(3, 1, part_one, input);
+    aoc::solve!(3, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -1,25 +1,25 @@
 use std::{ops::RangeInclusive, str::FromStr};
 
-use aoc::helpers::parse_lines;
+use aoc::{helpers::parse_lines, interval::RangeInclusiveExt};
 
 pub fn part_one(input: &str) -> Option<usize> {
     parse_lines::<RangePair>(input)
-        .filter(|RangePair(r1, r2)| fully_contains(r1, r2) || fully_contains(r2, r1))
+        .filter(|RangePair(r1, r2)| r1.contains_range(r2) || r2.contains_range(r1))
         .count()
         .into()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     parse_lines::<RangePair>(input)
-        .filter(|RangePair(r1, r2)| overlaps(r1, r2))
+        .filter(|RangePair(r1, r2)| r1.overlaps(r2))
         .count()
         .into()
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 4);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(4);
+    aoc::solve!(4, 1, part_one, input);
+    aoc::solve!(4, 2, part_two, input);
 }
 
 struct RangePair(RangeInclusive<u32>, RangeInclusive<u32>);
@@ -38,14 +38,6 @@ fn parse_range(s: &str) -> RangeInclusive<u32> {
     min.parse::<u32>().unwrap()..=max.parse::<u32>().unwrap()
 }
 
-fn fully_contains<T: PartialOrd>(r1: &RangeInclusive<T>, r2: &RangeInclusive<T>) -> bool {
-    r1.contains(r2.start()) && r1.contains(r2.end())
-}
-
-fn overlaps<T: PartialOrd>(r1: &RangeInclusive<T>, r2: &RangeInclusive<T>) -> bool {
-    r1.start() <= r2.end() && r2.start() <= r1.end()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
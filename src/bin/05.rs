@@ -33,9 +33,9 @@ fn parse_input(input: &str) -> (Stacks, impl Iterator<Item = Move> + '_) {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 5);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(5);
+    aoc::solve!(5, 1, part_one, input);
+    aoc::solve!(5, 2, part_two, input);
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
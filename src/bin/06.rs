@@ -38,9 +38,9 @@ fn calculate_forward_skip(bytes: &[u8]) -> usize {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 6);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(6);
+    aoc::solve!(6, 1, part_one, input);
+    aoc::solve!(6, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -49,19 +49,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(7));
-        assert_eq!(part_one(&"bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
-        assert_eq!(part_one(&"nppdvjthqldpwncqszvftbrmjlhg"), Some(6));
-        assert_eq!(part_one(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(10));
-        assert_eq!(part_one(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(11));
+        assert_eq!(part_one("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(7));
+        assert_eq!(part_one("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
+        assert_eq!(part_one("nppdvjthqldpwncqszvftbrmjlhg"), Some(6));
+        assert_eq!(part_one("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(10));
+        assert_eq!(part_one("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(19));
-        assert_eq!(part_two(&"bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
-        assert_eq!(part_two(&"nppdvjthqldpwncqszvftbrmjlhg"), Some(23));
-        assert_eq!(part_two(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
-        assert_eq!(part_two(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
+        assert_eq!(part_two("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(19));
+        assert_eq!(part_two("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
+        assert_eq!(part_two("nppdvjthqldpwncqszvftbrmjlhg"), Some(23));
+        assert_eq!(part_two("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
+        assert_eq!(part_two("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
     }
 }
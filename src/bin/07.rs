@@ -2,10 +2,19 @@ use std::str::FromStr;
 
 use aoc::helpers::parse_lines;
 
-pub fn part_one(input: &str) -> Option<u32> {
+aoc::params! {
+    pub struct Params {
+        /// Largest directory size counted in part one.
+        size_limit: u32 = 100_000,
+        disk_size: u32 = 70_000_000,
+        required_space: u32 = 30_000_000,
+    }
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let lines = parse_lines::<SessionLine>(input);
 
-    let threshold = 100000;
+    let threshold = params.size_limit;
     let mut sums = vec![0];
     let mut result = 0;
 
@@ -39,11 +48,11 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let lines: Vec<_> = parse_lines::<SessionLine>(input).collect();
 
-    let total = 70000000;
-    let needs = 30000000;
+    let total = params.disk_size;
+    let needs = params.required_space;
 
     let mut used = 0;
     for line in &lines {
@@ -87,9 +96,9 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 7);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let (input, params) = aoc::input::read_input_with_params::<Params>(7);
+    aoc::solve!(7, 1, part_one, &input, &params);
+    aoc::solve!(7, 2, part_two, &input, &params);
 }
 
 #[derive(Debug)]
@@ -135,12 +144,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
+        assert_eq!(part_one(&input, &Params::default()), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
+        assert_eq!(part_two(&input, &Params::default()), Some(24933642));
     }
 }
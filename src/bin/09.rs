@@ -1,6 +1,6 @@
 use std::{fmt::Debug, str::FromStr};
 
-use aoc::helpers::parse_lines;
+use aoc::{helpers::parse_lines, point::CardinalDirection};
 use hashbrown::HashSet;
 
 type Point = aoc::point::Point<i32>;
@@ -26,7 +26,7 @@ fn solve(input: &str, size: usize) -> usize {
 
     for m in movements {
         for _ in 0..m.steps {
-            knots[0] = knots[0].towards(m.direction.dx, m.direction.dy);
+            knots[0] = knots[0] + m.direction;
 
             for i in 1..knots.len() {
                 let prev = knots[i - 1];
@@ -49,7 +49,7 @@ fn solve(input: &str, size: usize) -> usize {
 
 #[derive(Debug, Clone, Copy)]
 struct Movement {
-    direction: Direction,
+    direction: CardinalDirection,
     steps: u8,
 }
 
@@ -65,30 +65,10 @@ impl FromStr for Movement {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Direction {
-    dx: i32,
-    dy: i32,
-}
-
-impl FromStr for Direction {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "U" => Direction { dx: 0, dy: 1 },
-            "D" => Direction { dx: 0, dy: -1 },
-            "L" => Direction { dx: -1, dy: 0 },
-            "R" => Direction { dx: 1, dy: 0 },
-            _ => panic!("invalid direction {}", s),
-        })
-    }
-}
-
 fn main() {
-    let input = &aoc::read_file("inputs", 9);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(9);
+    aoc::solve!(9, 1, part_one, input);
+    aoc::solve!(9, 2, part_two, input);
 }
 
 #[cfg(test)]
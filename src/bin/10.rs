@@ -15,7 +15,12 @@ pub fn part_one(input: &str) -> Option<i32> {
     Some(score)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str) -> anyhow::Result<String> {
+    aoc::ocr::recognize(&crt(input))
+}
+
+/// The `#`/`.` picture drawn on the CRT.
+fn crt(input: &str) -> String {
     let mut output = String::new();
 
     run(input, |cycles, x| {
@@ -31,7 +36,7 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     });
 
-    Some(output)
+    output
 }
 
 fn run<F>(input: &str, mut f: F)
@@ -61,9 +66,9 @@ where
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 10);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(10);
+    aoc::solve!(10, 1, part_one, input);
+    aoc::solve!(10, 2, part_two, input);
 }
 
 #[derive(Debug)]
@@ -97,23 +102,38 @@ mod tests {
     }
 
     #[test]
-    fn test_part_two() {
+    fn test_crt() {
         let input = aoc::read_file("examples", 10);
-
-        let output = part_two(&input);
-        println!("{}", output.unwrap());
         assert_eq!(
-            part_two(&input),
-            Some(
-                "##..##..##..##..##..##..##..##..##..##..
+            crt(&input),
+            "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######.....
 "
-                .to_owned()
-            )
         );
     }
+
+    #[test]
+    fn test_part_two() {
+        // the example draws a test pattern instead of letters
+        let input = aoc::read_file("examples", 10);
+        let error = part_two(&input).unwrap_err();
+        assert!(error.to_string().starts_with("unknown glyph"));
+    }
+
+    #[test]
+    fn test_recognize_crt_letters() {
+        let screen = "\
+###...##..#....###..###..####..##..#..#.
+#..#.#..#.#....#..#.#..#....#.#..#.#..#.
+#..#.#....#....#..#.###....#..#..#.#..#.
+###..#.##.#....###..#..#..#...####.#..#.
+#.#..#..#.#....#.#..#..#.#....#..#.#..#.
+#..#..###.####.#..#.###..####.#..#..##..
+";
+        assert_eq!(aoc::ocr::recognize(screen).unwrap(), "RGLRBZAU");
+    }
 }
@@ -180,9 +180,9 @@ impl FromStr for Op {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 11);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(11);
+    aoc::solve!(11, 1, part_one, input);
+    aoc::solve!(11, 2, part_two, input);
 }
 
 #[cfg(test)]
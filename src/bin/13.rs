@@ -125,9 +125,9 @@ fn compare_list_to_value(list_it: &mut PacketParser, value: u8) -> Ordering {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 13);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(13);
+    aoc::solve!(13, 1, part_one, input);
+    aoc::solve!(13, 2, part_two, input);
 }
 
 #[cfg(test)]
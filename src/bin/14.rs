@@ -1,5 +1,10 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
+use aoc::{
+    grid::InfiniteGrid,
+    image::FrameWriter,
+    render::{Animation, Canvas, Color, Draw, FnCanvas, Glyph},
+};
 use itertools::Itertools;
 
 type Coordinate = i16;
@@ -7,110 +12,142 @@ type Point = aoc::point::Point<Coordinate>;
 
 pub fn part_one(input: &str) -> Option<u32> {
     let scan = input.parse::<Scan>().unwrap();
-    let dims = scan.dimensions();
-
-    let mut grid = Grid::new(dims.min_x..=dims.max_x, dims.height);
-
-    grid.add_paths(&scan.paths);
+    let mut grid = Grid::new(&scan.paths);
+    Some(grid.pour(|_, _| {}))
+}
 
-    let mut fill_count = 0;
-    let start = Point { x: 500, y: 0 };
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(floored_grid(input).pour(|_, _| {}))
+}
 
-    let mut point = start;
-    let mut path = Vec::with_capacity(200);
-    loop {
-        let down = point.towards(0, 1);
-        let dl = point.towards(-1, 1);
-        let dr = point.towards(1, 1);
+fn floored_grid(input: &str) -> Grid {
+    let scan = input.parse::<Scan>().unwrap();
+    let mut grid = Grid::new(&scan.paths);
+    grid.height += 1;
+    grid.floor = true;
+    grid
+}
 
-        if down.y == grid.height {
-            break;
-        }
+/// Animates the sand of part two, one frame per grain coming to rest.
+fn animate(input: &str, animation: &mut Animation) {
+    let mut grid = floored_grid(input);
+    let count =
+        grid.pour(|grid, count| animation.frame(&grid.view(), &format!("{} grains", count)));
+    animation.finish(&grid.view(), &format!("{} grains", count));
+}
 
-        if grid.is_empty(&down) {
-            path.push(point);
-            point = down;
-        } else if grid.is_empty(&dl) {
-            path.push(point);
-            point = dl;
-        } else if grid.is_empty(&dr) {
-            path.push(point);
-            point = dr;
-        } else {
-            fill_count += 1;
-            grid.fill(&point);
-            point = path.pop().unwrap();
+/// Grains of sand per exported frame, which gives about a hundred frames for a full input.
+const FRAME_EVERY: u32 = 250;
+
+/// Writes the sand of part two as image frames, one per [`FRAME_EVERY`] grains coming to rest
+/// unless `--frame-every` is given.
+fn export_frames(input: &str, frames: &mut FrameWriter) -> anyhow::Result<()> {
+    let mut grid = floored_grid(input);
+    let mut result = Ok(());
+    grid.pour(|grid, _| {
+        if result.is_ok() {
+            result = frames.frame(&grid.view());
         }
-    }
-
-    Some(fill_count)
+    });
+    result?;
+    frames.finish(&grid.view())?;
+    Ok(())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let scan = input.parse::<Scan>().unwrap();
-    let dims = scan.dimensions();
-
-    let mut grid = Grid::new(
-        dims.min_x - dims.height..=dims.max_x + dims.height,
-        dims.height + 1,
-    );
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Tile {
+    #[default]
+    Air,
+    Rock,
+    Sand,
+}
 
-    grid.floor = true;
-    grid.add_paths(&scan.paths);
-
-    let mut fill_count = 0;
-    let mut path = Vec::with_capacity(200);
-    let mut point = Point { x: 500, y: 0 };
-    loop {
-        let down = point.towards(0, 1);
-        let dl = point.towards(-1, 1);
-        let dr = point.towards(1, 1);
-
-        if grid.is_empty(&down) {
-            path.push(point);
-            point = down;
-        } else if grid.is_empty(&dl) {
-            path.push(point);
-            point = dl;
-        } else if grid.is_empty(&dr) {
-            path.push(point);
-            point = dr;
-        } else {
-            fill_count += 1;
-            grid.fill(&point);
-            if let Some(up) = path.pop() {
-                point = up;
-            } else {
-                break;
-            }
+impl Draw for Tile {
+    fn glyph(&self) -> Glyph {
+        match self {
+            Tile::Air => Glyph::plain(' '),
+            Tile::Rock => Glyph::colored('#', Color::Gray),
+            Tile::Sand => Glyph::colored('o', Color::Yellow),
         }
     }
-
-    Some(fill_count)
 }
 
 struct Grid {
-    filled: Vec<bool>,
-    x_offset: Coordinate,
-    width: usize,
+    tiles: InfiniteGrid<Tile>,
     height: Coordinate,
     floor: bool,
 }
 
 impl Grid {
-    fn new(x_range: RangeInclusive<Coordinate>, height: Coordinate) -> Self {
-        let width = (x_range.end() - x_range.start() + 1) as usize;
-        Grid {
-            filled: vec![false; width * height as usize],
-            x_offset: *x_range.start(),
-            width,
-            height,
+    fn new(paths: &[Vec<Point>]) -> Self {
+        let mut grid = Grid {
+            tiles: InfiniteGrid::new(),
+            height: 0,
             floor: false,
-        }
+        };
+        grid.add_paths(paths);
+        grid.height = grid
+            .tiles
+            .bounds()
+            .map_or(0, |(_, max): (Point, _)| max.y + 1);
+        grid
+    }
+
+    /// The area the sand of part two can reach, which does not change while it is poured.
+    fn view(&self) -> impl Canvas + '_ {
+        let (height, source_x) = (self.height as i64, 500);
+        let min = aoc::point::Point::new(source_x - height, 0);
+        let max = aoc::point::Point::new(source_x + height, height);
+        FnCanvas::new(min, max, move |p| {
+            if self.floor && p.y == height {
+                Tile::Rock.glyph()
+            } else {
+                self.tiles[p].glyph()
+            }
+        })
     }
 
     fn is_empty(&self, p: &Point) -> bool {
-        (!self.floor || p.y < self.height) && !self.filled[self.index(p.x, p.y)]
+        (!self.floor || p.y < self.height) && self.tiles[*p] == Tile::Air
+    }
+
+    /// Lets sand fall until it flows into the abyss or blocks the source, and returns the
+    /// number of grains that came to rest. `on_rest` is called after each of them.
+    fn pour(&mut self, mut on_rest: impl FnMut(&Self, u32)) -> u32 {
+        let mut fill_count = 0;
+        let mut path = Vec::with_capacity(200);
+        let mut point = Point { x: 500, y: 0 };
+        loop {
+            let down = point.towards(0, 1);
+            let dl = point.towards(-1, 1);
+            let dr = point.towards(1, 1);
+
+            if !self.floor && down.y == self.height {
+                break;
+            }
+
+            if self.is_empty(&down) {
+                path.push(point);
+                point = down;
+            } else if self.is_empty(&dl) {
+                path.push(point);
+                point = dl;
+            } else if self.is_empty(&dr) {
+                path.push(point);
+                point = dr;
+            } else {
+                fill_count += 1;
+                self.tiles[point] = Tile::Sand;
+                on_rest(self, fill_count);
+                if let Some(up) = path.pop() {
+                    point = up;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        fill_count
     }
 
     fn add_paths(&mut self, paths: &[Vec<Point>]) {
@@ -118,57 +155,22 @@ impl Grid {
             for (a, b) in path.iter().tuple_windows() {
                 if a.x == b.x {
                     for y in a.y.min(b.y)..=a.y.max(b.y) {
-                        self.fill(&Point { x: a.x, y });
+                        self.tiles[Point { x: a.x, y }] = Tile::Rock;
                     }
                 } else {
                     for x in a.x.min(b.x)..=a.x.max(b.x) {
-                        self.fill(&Point { x, y: a.y });
+                        self.tiles[Point { x, y: a.y }] = Tile::Rock;
                     }
                 }
             }
         }
     }
-
-    fn fill(&mut self, p: &Point) {
-        let i = self.index(p.x, p.y);
-        self.filled[i] = true;
-    }
-
-    fn index(&self, x: Coordinate, y: Coordinate) -> usize {
-        y as usize * self.width + ((x - self.x_offset) as usize)
-    }
 }
 
 struct Scan {
     paths: Vec<Vec<Point>>,
 }
 
-struct ScanDimensions {
-    min_x: Coordinate,
-    max_x: Coordinate,
-    height: Coordinate,
-}
-
-impl Scan {
-    fn dimensions(&self) -> ScanDimensions {
-        let mut min_x = Coordinate::MAX;
-        let mut max_x = Coordinate::MIN;
-        let mut max_y = Coordinate::MIN;
-
-        for p in self.paths.iter().flatten() {
-            min_x = min_x.min(p.x);
-            max_x = max_x.max(p.x);
-            max_y = max_y.max(p.y);
-        }
-
-        ScanDimensions {
-            min_x: min_x - 1,
-            max_x: max_x + 1,
-            height: max_y + 1,
-        }
-    }
-}
-
 impl FromStr for Scan {
     type Err = anyhow::Error;
 
@@ -183,9 +185,19 @@ impl FromStr for Scan {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 14);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(14);
+    if let Some(mut animation) = Animation::from_env() {
+        animate(input, &mut animation);
+        return;
+    }
+    if let Some(mut frames) = FrameWriter::from_env_every(FRAME_EVERY) {
+        if let Err(e) = export_frames(input, &mut frames) {
+            eprintln!("{:#}", e);
+        }
+        return;
+    }
+    aoc::solve!(14, 1, part_one, input);
+    aoc::solve!(14, 2, part_two, input);
 }
 
 #[cfg(test)]
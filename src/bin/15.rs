@@ -1,23 +1,53 @@
-use anyhow::anyhow;
-use aoc::helpers::parse_lines;
-
-use itertools::Itertools;
+use anyhow::{anyhow, bail};
+use aoc::{
+    diamond::{from_rotated, Diamond},
+    helpers::parse_lines,
+    image::{FrameWriter, Svg},
+    interval::IntervalSet,
+    render::Color,
+};
+
+use itertools::{iproduct, Itertools};
 use lazy_static::lazy_static;
 use rayon::prelude::*;
 use regex::Regex;
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
 type Point = aoc::point::Point<i32>;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    part_one_y(input, 2000000)
+aoc::params! {
+    pub struct Params {
+        /// Row on which to count the positions that cannot contain a beacon.
+        y: i32 = 2_000_000,
+        /// Upper bound of both coordinates of the distress beacon.
+        max: i32 = 4_000_000,
+        /// How part two searches for the distress beacon.
+        solver: Solver = Solver::Geometric,
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    part_two_max(input, 4_000_000)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+    /// Scans every row in parallel for a gap between the ranges covered by the sensors.
+    Scan,
+    /// Checks the points where the lines just outside of the sensor diamonds cross.
+    Geometric,
+}
+
+impl FromStr for Solver {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scan" => Ok(Solver::Scan),
+            "geometric" => Ok(Solver::Geometric),
+            _ => bail!("invalid solver '{}'", s),
+        }
+    }
 }
 
-fn part_one_y(input: &str, y: i32) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let y = params.y;
     let sensors: Vec<_> = parse_lines::<SensorData>(input).collect();
     let beacons_on_line = sensors
         .iter()
@@ -27,73 +57,76 @@ fn part_one_y(input: &str, y: i32) -> Option<u32> {
         .dedup()
         .count();
 
-    let mut ranges: Vec<_> = sensors.iter().flat_map(|s| s.range_on_line(y)).collect();
-    ranges.sort_by_key(|r| *r.start());
-
-    let mut count = 0;
-    let mut prev_end = i32::min_value();
-
-    for r in &ranges {
-        let start = (*r.start()).max(prev_end + 1);
-        let end = *r.end();
-        if start <= end {
-            count += (end - start + 1) as u32;
-        }
-
-        prev_end = end.max(prev_end);
-    }
-
-    Some(count - beacons_on_line as u32)
+    let covered: IntervalSet<i32> = sensors.iter().flat_map(|s| s.area.clip_row(y)).collect();
+    Some(covered.len() as u32 - beacons_on_line as u32)
 }
 
-fn part_two_max(input: &str, max: i32) -> Option<u64> {
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
     let sensors: Vec<_> = parse_lines::<SensorData>(input).collect();
+    let beacon = match params.solver {
+        Solver::Scan => scan(&sensors, params.max),
+        Solver::Geometric => intersect_edges(&sensors, params.max),
+    }?;
+    Some(4000000 * (beacon.x as u64) + (beacon.y as u64))
+}
 
+fn scan(sensors: &[SensorData], max: i32) -> Option<Point> {
     (0..=max).into_par_iter().find_map_any(|y| {
-        let mut ranges: Vec<_> = sensors.iter().flat_map(|s| s.range_on_line(y)).collect();
-        ranges.sort_by_key(|r| *r.start());
-
-        let mut prev_end = 0;
-        for r in ranges.iter() {
-            let last_start = *r.start();
-
-            if prev_end + 1 < last_start && last_start > 0 && prev_end < max {
-                let x = prev_end + 1;
-                return Some(4000000 * (x as u64) + (y as u64));
-            }
-            prev_end = prev_end.max(*r.end());
-        }
-
-        None
+        let covered: IntervalSet<i32> = sensors.iter().flat_map(|s| s.area.clip_row(y)).collect();
+        let x = covered.gaps_within(0..=max).next()?.start;
+        Some(Point::new(x, y))
     })
 }
 
+/// The distress beacon is the only position of the area that no sensor covers, so it lies just
+/// outside of the diamonds around it, or of the ones along the border of the area. In rotated
+/// coordinates, the edges of the diamonds are lines of constant `u = x + y` or `v = x - y` and
+/// the beacon is where two of them, or one of them and the border, cross.
+fn intersect_edges(sensors: &[SensorData], max: i32) -> Option<Point> {
+    let mut us = Vec::new();
+    let mut vs = Vec::new();
+    for s in sensors {
+        let (min, max) = s.area.grow(1).rotated_bounds();
+        us.extend([min.x, max.x]);
+        vs.extend([min.y, max.y]);
+    }
+    us.sort_unstable();
+    us.dedup();
+    vs.sort_unstable();
+    vs.dedup();
+
+    let crossings = iproduct!(&us, &vs).filter_map(|(&u, &v)| from_rotated(Point::new(u, v)));
+    let on_border = us
+        .iter()
+        .flat_map(|&u| [(0, u), (max, u - max), (u, 0), (u - max, max)])
+        .chain(
+            vs.iter()
+                .flat_map(|&v| [(0, -v), (max, max - v), (v, 0), (v + max, max)]),
+        )
+        .chain([(0, 0), (max, 0), (0, max), (max, max)])
+        .map(|(x, y)| Point::new(x, y));
+
+    let area = 0..=max;
+    crossings
+        .chain(on_border)
+        .filter(|p| area.contains(&p.x) && area.contains(&p.y))
+        .find(|&p| sensors.iter().all(|s| !s.area.contains(p)))
+}
+
 #[derive(Debug)]
 struct SensorData {
-    point: Point,
+    /// The positions at least as close to the sensor as its beacon, so without other beacons.
+    area: Diamond<i32>,
     beacon: Point,
-    min_range: u32,
 }
 
 impl SensorData {
     fn new(point: Point, beacon: Point) -> Self {
         SensorData {
-            min_range: point.manhattan_distance(&beacon),
-            point,
+            area: Diamond::through(point, beacon),
             beacon,
         }
     }
-
-    fn range_on_line(&self, y: i32) -> Option<RangeInclusive<i32>> {
-        let distance = y.abs_diff(self.point.y);
-        if distance < self.min_range {
-            let d = (self.min_range - distance) as i32;
-
-            Some(self.point.x - d..=self.point.x + d)
-        } else {
-            None
-        }
-    }
 }
 
 impl FromStr for SensorData {
@@ -122,25 +155,49 @@ impl FromStr for SensorData {
     }
 }
 
-fn main() {
-    let input = &aoc::read_file("inputs", 15);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+/// Draws the sensor diamonds, the row of part one and the distress beacon found in part two.
+fn export_svg(input: &str, params: &Params, frames: &mut FrameWriter) -> anyhow::Result<()> {
+    let sensors: Vec<_> = parse_lines::<SensorData>(input).collect();
+    let f = |p: Point| aoc::point::Point::new(p.x as f64, p.y as f64);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let (mut min, mut max) = (Point::new(0, 0), Point::new(params.max, params.max));
+    for s in &sensors {
+        let (c, r) = (s.area.center, s.area.radius);
+        min = Point::new(min.x.min(c.x - r), min.y.min(c.y - r));
+        max = Point::new(max.x.max(c.x + r), max.y.max(c.y + r));
+    }
+
+    let mut svg = Svg::new(f(min), f(max));
+    let dot = svg.hairline() * 4.0;
+    let area = params.max as f64;
+    svg.rect(f(Point::new(0, 0)), area, area, Color::Gray);
 
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 15);
-        assert_eq!(part_one_y(&input, 10), Some(26));
+    for s in &sensors {
+        svg.polygon(&s.area.corners().map(f), Color::Cyan);
+        svg.circle(f(s.area.center), dot, Color::Green);
+        svg.circle(f(s.beacon), dot, Color::Yellow);
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 15);
-        assert_eq!(part_two_max(&input, 20), Some(56000011));
+    let y = params.y;
+    svg.line(f(Point::new(min.x, y)), f(Point::new(max.x, y)), Color::Red);
+    if let Some(answer) = part_two(input, params) {
+        let beacon = Point::new((answer / 4000000) as i32, (answer % 4000000) as i32);
+        svg.circle(f(beacon), dot * 2.0, Color::Red);
     }
+
+    frames.svg_frame(&svg)
 }
+
+fn main() {
+    let (input, params) = aoc::input::read_input_with_params::<Params>(15);
+    if let Some(mut frames) = FrameWriter::from_env() {
+        if let Err(e) = export_svg(&input, &params, &mut frames) {
+            eprintln!("{:#}", e);
+        }
+        return;
+    }
+    aoc::solve!(15, 1, part_one, &input, &params);
+    aoc::solve!(15, 2, part_two, &input, &params);
+}
+
+aoc::example_tests!(15, params);
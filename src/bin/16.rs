@@ -1,25 +1,32 @@
 use std::{cmp::Reverse, hash::Hash, str::FromStr};
 
+use aoc::all_pairs::DistanceMatrix;
 use aoc::helpers::parse_lines;
 use hashbrown::HashMap;
-use itertools::iproduct;
 
-pub fn part_one(input: &str) -> Option<u16> {
+aoc::params! {
+    pub struct Params {
+        minutes: u8 = 30,
+        /// Time left after teaching the elephant in part two.
+        minutes_with_elephant: u8 = 26,
+    }
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<u16> {
     let valves = ValveMap::new(input);
-    let answer = valves.search(30);
+    let answer = valves.search(params.minutes);
     answer.iter().copied().max()
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
+pub fn part_two(input: &str, params: &Params) -> Option<u16> {
     let valves = ValveMap::new(input);
-    let answer = valves.search(26);
+    let answer = valves.search(params.minutes_with_elephant);
 
     let threshold = answer.iter().copied().max().unwrap() / 2;
     let answers = answer
         .iter()
         .copied()
         .enumerate()
-        .map(|(k, v)| (k, v))
         .filter(|(_, v)| *v > threshold)
         .collect::<Vec<_>>();
 
@@ -37,13 +44,11 @@ pub fn part_two(input: &str) -> Option<u16> {
 struct ValveMap {
     start_id: ValveId,
     valves: Vec<Valve>,
-    distances: Vec<u8>,
-    keys: usize,
+    distances: DistanceMatrix<u8>,
 }
 
 impl ValveMap {
     fn new(input: &str) -> ValveMap {
-        let mut valve_map = HashMap::<ValveId, Valve>::new();
         let mut id_mapping = ValveIdMapping::new();
         let mut valves = parse_lines::<ValveData>(input).collect::<Vec<_>>();
 
@@ -54,51 +59,25 @@ impl ValveMap {
             id_mapping.get_id(&v.id);
         }
 
-        for v in &valves {
-            let v = v.to_valve(&mut id_mapping);
-            valve_map.insert(v.id, v);
-        }
-
-        let keys = valve_map.keys().map(|k| k.0).max().unwrap() as usize + 1;
-        let mut distances = vec![u8::MAX; keys * keys];
-
-        // Initial distances
-        for valve in valve_map.values() {
-            for tunnel in &valve.tunnels {
-                distances[valve.id.0 as usize * keys + tunnel.0 as usize] = 1;
-            }
-        }
-
-        // Self-distances to zero
-        for i in 0..keys {
-            distances[i * keys + i] = 0;
-        }
-
-        // Floyd-Warshall
-        for (k, i, j) in iproduct!(0..keys, 0..keys, 0..keys) {
-            let ij = i * keys + j;
-            let ik = i * keys + k;
-            let kj = k * keys + j;
-
-            let sum = distances[ik].saturating_add(distances[kj]);
-            if distances[ij] > sum {
-                distances[ij] = sum;
-            }
-        }
-
-        let valves = valve_map
-            .drain()
-            .filter(|v| v.1.rate != 0)
-            .map(|v| v.1)
+        let mut valves: Vec<_> = valves.iter().map(|v| v.to_valve(&mut id_mapping)).collect();
+        let adjacency: Vec<Vec<usize>> = valves
+            .iter()
+            .map(|v| v.tunnels.iter().map(|t| t.0 as usize).collect())
             .collect();
 
-        let start_id = id_mapping.get_id("AA");
+        // Only the distances between the non-zero valves and from the start are needed
+        valves.retain(|v| v.rate != 0);
+        let mut nodes: Vec<usize> = (0..valves.len()).collect();
+        let mut start_id = id_mapping.get_id("AA");
+        if start_id.0 as usize >= valves.len() {
+            nodes.push(start_id.0 as usize);
+            start_id = ValveId(valves.len() as u8);
+        }
 
         ValveMap {
             start_id,
             valves,
-            distances,
-            keys,
+            distances: DistanceMatrix::floyd_warshall(&adjacency).compress(&nodes),
         }
     }
 
@@ -154,7 +133,7 @@ impl ValveMap {
     }
 
     fn distance_between(&self, i: ValveId, j: ValveId) -> u8 {
-        self.distances[i.0 as usize * self.keys + j.0 as usize]
+        self.distances.get(i.0 as usize, j.0 as usize)
     }
 }
 
@@ -243,9 +222,9 @@ impl ValveIdMapping {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 16);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let (input, params) = aoc::input::read_input_with_params::<Params>(16);
+    aoc::solve!(16, 1, part_one, &input, &params);
+    aoc::solve!(16, 2, part_two, &input, &params);
 }
 
 #[cfg(test)]
@@ -255,12 +234,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", 16);
-        assert_eq!(part_one(&input), Some(1651));
+        assert_eq!(part_one(&input, &Params::default()), Some(1651));
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", 16);
-        assert_eq!(part_two(&input), Some(1707));
+        assert_eq!(part_two(&input, &Params::default()), Some(1707));
     }
 }
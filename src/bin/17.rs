@@ -1,16 +1,20 @@
+use anyhow::{bail, ensure};
+use aoc::render::{Animation, Canvas, Color, FnCanvas, Glyph};
 use hashbrown::HashMap;
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut tower = Tower::new(input);
+type Point = aoc::point::Point<i64>;
+
+pub fn part_one(input: &str) -> anyhow::Result<u64> {
+    let mut tower = Tower::new(input)?;
 
     tower.run_steps(2022);
 
-    Some(tower.height as u64)
+    Ok(tower.height as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut tower = Tower::new(input);
-    Some(simulate_fast(&mut tower, 1000000000000))
+pub fn part_two(input: &str) -> anyhow::Result<u64> {
+    let mut tower = Tower::new(input)?;
+    Ok(simulate_fast(&mut tower, 1000000000000))
 }
 
 fn simulate_fast(tower: &mut Tower, total_rocks: u64) -> u64 {
@@ -55,12 +59,12 @@ enum Move {
 }
 
 impl Move {
-    fn from(c: char) -> Self {
-        match c {
+    fn from(c: char) -> anyhow::Result<Self> {
+        Ok(match c {
             '<' => Self::Left,
             '>' => Self::Right,
-            _ => panic!("unexpected '{}'", c),
-        }
+            _ => bail!("unexpected jet '{}'", c),
+        })
     }
 
     fn delta_x(self) -> i8 {
@@ -139,27 +143,41 @@ struct Tower {
 }
 
 impl Tower {
-    fn new(input: &str) -> Self {
-        Self {
-            rows: vec![0; 10_000],
+    fn new(input: &str) -> anyhow::Result<Self> {
+        let moves = input
+            .trim_end()
+            .chars()
+            .map(Move::from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        ensure!(!moves.is_empty(), "no jets in the input");
+
+        Ok(Self {
+            rows: Vec::new(),
             height: 0,
             shape_index: 0,
-            moves: input.chars().map(Move::from).collect(),
+            moves,
             move_index: 0,
-        }
+        })
     }
 
     fn run_steps(&mut self, rocks: u64) {
+        self.run_steps_with(rocks, |_, _| {});
+    }
+
+    /// Drops `rocks` rocks, calling `on_move` whenever the falling rock was pushed or fell.
+    fn run_steps_with(&mut self, rocks: u64, mut on_move: impl FnMut(&Tower, &Rock)) {
         for _ in 0..rocks {
             let mut rock = Rock::new(self.height, self.next_shape());
 
             loop {
                 rock.try_move(self.next_move(), self);
+                on_move(self, &rock);
 
                 if !rock.try_fall(self) {
                     self.add_rock(rock);
                     break;
                 }
+                on_move(self, &rock);
             }
         }
     }
@@ -194,8 +212,8 @@ impl Tower {
     fn set(&mut self, x: i8, y: i32) {
         let i = y as usize;
 
-        while i >= self.rows.len() {
-            self.rows.push(0);
+        if i >= self.rows.len() {
+            self.rows.resize(i + 1, 0);
         }
 
         self.height = self.height.max(y + 1);
@@ -216,6 +234,35 @@ impl Tower {
         (row & (1 << x)) == 0
     }
 
+    /// The top of the tower with the falling rock, walls and floor, drawn upside down so that
+    /// the tower grows upwards.
+    fn view<'a>(&'a self, rock: Option<&'a Rock>) -> impl Canvas + 'a {
+        let top = rock.map_or(self.height, |r| r.y + 4) as i64;
+        let min = Point::new(-1, 0);
+        let max = Point::new(7, ANIMATION_ROWS.min(top + 1));
+
+        FnCanvas::new(min, max, move |p| {
+            let y = top - p.y;
+            let falling = rock.is_some_and(|r| {
+                r.shape
+                    .iter()
+                    .any(|&(dx, dy)| (r.x + dx) as i64 == p.x && (r.y + dy) as i64 == y)
+            });
+
+            if falling {
+                Glyph::colored('@', Color::Yellow)
+            } else if y < 0 {
+                Glyph::plain(if p.x == -1 || p.x == 7 { '+' } else { '-' })
+            } else if p.x == -1 || p.x == 7 {
+                Glyph::plain('|')
+            } else if !self.is_free(p.x as i8, y as i32) {
+                Glyph::colored('#', Color::Gray)
+            } else {
+                Glyph::plain('.')
+            }
+        })
+    }
+
     fn tower_state(&self) -> u32 {
         if self.height < 3 {
             return 0;
@@ -229,10 +276,29 @@ impl Tower {
     }
 }
 
+/// Rows of the tower shown when animating, counted from the top of the falling rock.
+const ANIMATION_ROWS: i64 = 30;
+
+/// Animates the first rocks of part one.
+fn animate(input: &str, animation: &mut Animation) -> anyhow::Result<()> {
+    let mut tower = Tower::new(input)?;
+    tower.run_steps_with(2022, |tower, rock| {
+        animation.frame(&tower.view(Some(rock)), &format!("height {}", tower.height))
+    });
+    animation.finish(&tower.view(None), &format!("height {}", tower.height));
+    Ok(())
+}
+
 fn main() {
-    let input = &aoc::read_file("inputs", 17);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(17);
+    if let Some(mut animation) = Animation::from_env() {
+        if let Err(e) = animate(input, &mut animation) {
+            eprintln!("{:#}", e);
+        }
+        return;
+    }
+    aoc::solve!(17, 1, part_one, input);
+    aoc::solve!(17, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -242,12 +308,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", 17);
-        assert_eq!(part_one(&input), Some(3068));
+        assert_eq!(part_one(&input).unwrap(), 3068);
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", 17);
-        assert_eq!(part_two(&input), Some(1514285714288));
+        assert_eq!(part_two(&input).unwrap(), 1514285714288);
     }
 }
@@ -242,9 +242,9 @@ impl FromStr for Blueprint {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 19);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(19);
+    aoc::solve!(19, 1, part_one, input);
+    aoc::solve!(19, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -176,9 +176,9 @@ impl NumList {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 20);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(20);
+    aoc::solve!(20, 1, part_one, input);
+    aoc::solve!(20, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -190,9 +190,9 @@ impl FromStr for Assignment {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 21);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(21);
+    aoc::solve!(21, 1, part_one, input);
+    aoc::solve!(21, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -1,17 +1,64 @@
-use aoc::point::CardinalDirection;
-use bitset::BitSet;
+use anyhow::Context;
+use aoc::{
+    grid::InfiniteGrid,
+    image::FrameWriter,
+    point::CardinalDirection,
+    render::{Animation, Canvas, Color, FnCanvas, Glyph},
+};
 use itertools::Itertools;
 use CardinalDirection::*;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(run(input, 10, false))
+    Some(run(input, 10, false, |_, _, _| {}))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(run(input, 1_000_000, true))
+    Some(run(input, 1_000_000, true, |_, _, _| {}))
 }
 
-pub fn run(input: &str, rounds: u32, round_count: bool) -> u32 {
+/// Animates the elves spreading out until none of them moves.
+fn animate(input: &str, animation: &mut Animation) {
+    run(input, 1_000_000, true, |elves, round, moved| {
+        let caption = format!("round {}: {} elves moved", round, moved);
+        if moved == 0 {
+            animation.finish(elves, &caption);
+        } else {
+            animation.frame(elves, &caption);
+        }
+    });
+}
+
+/// Writes the elves as image frames, one per round, all covering the area of the last round.
+fn export_frames(input: &str, frames: &mut FrameWriter) -> anyhow::Result<()> {
+    let mut extent = None;
+    run(input, 1_000_000, true, |elves, _, moved| {
+        if moved == 0 {
+            extent = elves.extent();
+        }
+    });
+    let (min, max) = extent.context("no elves")?;
+
+    let mut result = Ok(());
+    run(input, 1_000_000, true, |elves, _, moved| {
+        let canvas = FnCanvas::new(min, max, |p| elves.glyph(p));
+        if result.is_ok() {
+            result = frames.frame(&canvas);
+        }
+        if result.is_ok() && moved == 0 {
+            result = frames.finish(&canvas);
+        }
+    });
+    result
+}
+
+/// Runs the rounds, calling `on_round` with the elves and the number of moved elves after each
+/// of them.
+pub fn run(
+    input: &str,
+    rounds: u32,
+    round_count: bool,
+    mut on_round: impl FnMut(&ElfSet, u32, usize),
+) -> u32 {
     let mut elves = parse_elves(input);
     let mut elf_points = ElfSet::new(&elves);
     let mut proposals_counts = ProposalMap::new();
@@ -70,6 +117,8 @@ pub fn run(input: &str, rounds: u32, round_count: bool) -> u32 {
             elf_points.add(new);
         }
 
+        on_round(&elf_points, r + 1, moves.len());
+
         if moves.is_empty() && round_count {
             return r + 1;
         }
@@ -86,61 +135,48 @@ pub fn run(input: &str, rounds: u32, round_count: bool) -> u32 {
 type Coord = i8;
 type Point = aoc::point::Point<Coord>;
 
+/// Number of elves proposing each point, saturating at 2.
 struct ProposalMap {
-    proposals: Vec<u8>,
-}
-
-const MAX_POINT_INDEX: usize = 4 * 80000;
-const INDEX_X_OFFSET: usize = 256;
-const INDEX_Y_OFFSET: usize = 256;
-const INDEX_STRIDE: usize = 512;
-
-#[inline]
-fn point_index(point: Point) -> usize {
-    let y = (point.y as isize + INDEX_Y_OFFSET as isize) as usize;
-    let x = (point.x as isize + INDEX_X_OFFSET as isize) as usize;
-    y * INDEX_STRIDE + x
+    proposals: InfiniteGrid<u8>,
 }
 
 impl ProposalMap {
     fn new() -> Self {
         ProposalMap {
-            proposals: vec![0; MAX_POINT_INDEX / 4],
+            proposals: InfiniteGrid::new(),
         }
     }
 
     fn clear(&mut self) {
-        self.proposals.fill(0);
+        self.proposals.clear();
     }
 
     fn propose(&mut self, point: Point) {
-        let (array_index, bit_index) = self.index(point);
-
-        let byte = self.proposals[array_index];
-        let value = (byte >> bit_index) & 3;
-        if value == 0 {
-            self.proposals[array_index] = byte | (1 << bit_index);
-        } else if value == 1 {
-            self.proposals[array_index] = byte | (2 << bit_index);
-        }
+        let count = &mut self.proposals[point];
+        *count = (*count + 1).min(2);
     }
 
     fn has_single_proposal(&self, point: Point) -> bool {
-        let (array_index, bit_index) = self.index(point);
-
-        ((self.proposals[array_index] >> bit_index) & 3) == 1
+        self.proposals[point] == 1
     }
+}
 
-    #[inline]
-    fn index(&self, point: Point) -> (usize, usize) {
-        let index = point_index(point);
-        let array_index = index / 4;
-        (array_index, 2 * (index % 4))
-    }
+pub struct ElfSet {
+    points: InfiniteGrid<bool>,
 }
 
-struct ElfSet {
-    points: BitSet,
+impl Canvas for ElfSet {
+    fn extent(&self) -> Option<(aoc::point::Point<i64>, aoc::point::Point<i64>)> {
+        self.points.bounds()
+    }
+
+    fn glyph(&self, p: aoc::point::Point<i64>) -> Glyph {
+        if self.points[p] {
+            Glyph::colored('#', Color::Green)
+        } else {
+            Glyph::plain('.')
+        }
+    }
 }
 
 const BIT_N: u8 = 0;
@@ -160,7 +196,7 @@ const MASK_WEST: u8 = (1 << BIT_W) | (1 << BIT_SW) | (1 << BIT_NW);
 impl ElfSet {
     fn new(elves: &[Point]) -> Self {
         let mut result = ElfSet {
-            points: BitSet::with_capacity(MAX_POINT_INDEX),
+            points: InfiniteGrid::new(),
         };
 
         for &elf in elves {
@@ -171,24 +207,29 @@ impl ElfSet {
     }
 
     fn add(&mut self, point: Point) {
-        self.points.set(point_index(point), true);
+        // keep the neighbours allocated so that `proposal` can read them by index
+        self.points.reserve(point.towards(-1, -1));
+        self.points.reserve(point.towards(1, 1));
+        self.points[point] = true;
     }
 
     fn remove(&mut self, point: Point) {
-        self.points.set(point_index(point), false);
+        self.points[point] = false;
     }
 
     fn proposal(&self, p: Point, directions: &[(CardinalDirection, u8)]) -> Option<Point> {
-        let index = point_index(p);
-
-        let n = self.points.test(index - INDEX_STRIDE) as u8;
-        let s = self.points.test(index + INDEX_STRIDE) as u8;
-        let w = self.points.test(index - 1) as u8;
-        let e = self.points.test(index + 1) as u8;
-        let nw = self.points.test(index - INDEX_STRIDE - 1) as u8;
-        let sw = self.points.test(index + INDEX_STRIDE - 1) as u8;
-        let ne = self.points.test(index - INDEX_STRIDE + 1) as u8;
-        let se = self.points.test(index + INDEX_STRIDE + 1) as u8;
+        let index = self.points.index_of(p).unwrap();
+        let stride = self.points.width();
+        let test = |i: usize| self.points.cells()[i] as u8;
+
+        let n = test(index - stride);
+        let s = test(index + stride);
+        let w = test(index - 1);
+        let e = test(index + 1);
+        let nw = test(index - stride - 1);
+        let sw = test(index + stride - 1);
+        let ne = test(index - stride + 1);
+        let se = test(index + stride + 1);
 
         let bits = (n << BIT_N)
             | (s << BIT_S)
@@ -239,9 +280,19 @@ fn parse_elves(s: &str) -> Vec<Point> {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 23);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(23);
+    if let Some(mut animation) = Animation::from_env() {
+        animate(input, &mut animation);
+        return;
+    }
+    if let Some(mut frames) = FrameWriter::from_env() {
+        if let Err(e) = export_frames(input, &mut frames) {
+            eprintln!("{:#}", e);
+        }
+        return;
+    }
+    aoc::solve!(23, 1, part_one, input);
+    aoc::solve!(23, 2, part_two, input);
 }
 
 #[cfg(test)]
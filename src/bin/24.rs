@@ -1,17 +1,20 @@
-use std::cmp::Reverse;
 use std::iter::once;
 
+use anyhow::{bail, ensure};
+use aoc::{
+    render::{Animation, Canvas, Color, FnCanvas, Glyph},
+    shortest_path::{a_star, a_star_with_path, DenseScores, Graph},
+};
 use hashbrown::HashMap;
-use priority_queue::PriorityQueue;
 
 use TripState::{Initial, VisitedEnd, VisitedStartAfterEnd};
 
-pub fn part_one(input: &str) -> Option<u16> {
-    Basin::parse(input, false).shortest_path()
+pub fn part_one(input: &str) -> anyhow::Result<Option<u16>> {
+    Ok(Basin::parse(input, false)?.shortest_path())
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
-    Basin::parse(input, true).shortest_path()
+pub fn part_two(input: &str) -> anyhow::Result<Option<u16>> {
+    Ok(Basin::parse(input, true)?.shortest_path())
 }
 
 type Minutes = i16;
@@ -21,7 +24,7 @@ const DIRECTIONS: [(Coordinate, Coordinate); 4] = [(-1, 0), (1, 0), (0, -1), (0,
 
 type Point = aoc::point::Point<Coordinate>;
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 struct SearchState {
     pos: Point,
 
@@ -109,8 +112,28 @@ struct Basin {
 }
 
 impl Basin {
-    fn parse(s: &str, go_back_to_start: bool) -> Self {
+    fn parse(s: &str, go_back_to_start: bool) -> anyhow::Result<Self> {
         let lines: Vec<_> = s.lines().filter(|l| l.starts_with('#')).collect();
+        ensure!(
+            lines.len() > 2,
+            "expected a walled basin, got {} wall lines",
+            lines.len()
+        );
+        let line_width = lines[0].len();
+        ensure!(
+            line_width > 2,
+            "expected a walled basin, got a wall of width {}",
+            line_width
+        );
+        for (i, l) in lines.iter().enumerate() {
+            ensure!(
+                l.len() == line_width,
+                "line {}: expected width {}, got {}",
+                i + 1,
+                line_width,
+                l.len()
+            );
+        }
 
         let mut blizzards_by_row = HashMap::<Coordinate, (Vec<Blizzard>, Vec<Blizzard>)>::new();
         let mut blizzards_by_col = HashMap::<Coordinate, (Vec<Blizzard>, Vec<Blizzard>)>::new();
@@ -146,15 +169,15 @@ impl Basin {
                             .or_insert_with(|| (Vec::new(), Vec::new()))
                             .1
                             .push(Blizzard::new(pos)),
-                        _ => panic!("invalid direction {c}"),
+                        _ => bail!("line {}: invalid direction '{}'", y + 2, c as char),
                     }
                 }
             }
         }
 
-        let width = (lines[0].len() - 2) as Coordinate;
+        let width = (line_width - 2) as Coordinate;
         let height = (lines.len() - 2) as Coordinate;
-        Basin {
+        Ok(Basin {
             start: Point { x: 0, y: -1 },
             end: Point {
                 x: width - 1,
@@ -165,7 +188,7 @@ impl Basin {
             blizzards_by_row,
             blizzards_by_col,
             go_back_to_start,
-        }
+        })
     }
 
     fn is_empty(&self, p: Point, minutes: Minutes) -> bool {
@@ -200,35 +223,82 @@ impl Basin {
     }
 
     fn shortest_path(&self) -> Option<u16> {
-        let start = SearchState::new(self.start, 0, Initial);
-
-        let mut g_score = vec![u16::MAX; MAX_HEIGHT * MAX_WIDTH * MAX_MINUTES * MAX_TRIP_STATE];
-        let mut open_set = PriorityQueue::<SearchState, Reverse<u16>>::new();
+        a_star(self, once(self.start_state()), Self::scores()).map(|(end, _)| end.minutes() as u16)
+    }
 
-        g_score[start.index()] = 0;
-        let start_distance = self.heuristic_distance(&start);
-        open_set.push(start, Reverse(start_distance));
+    fn start_state(&self) -> SearchState {
+        SearchState::new(self.start, 0, Initial)
+    }
 
-        while let Some((current, _)) = open_set.pop() {
-            let current_gscore = current.minutes() as u16;
+    fn scores() -> DenseScores<u16, fn(&SearchState) -> usize> {
+        DenseScores::new(
+            MAX_HEIGHT * MAX_WIDTH * MAX_MINUTES * MAX_TRIP_STATE,
+            SearchState::index,
+        )
+    }
 
-            if current.pos == self.end
-                && (!self.go_back_to_start || current.state() == VisitedStartAfterEnd)
-            {
-                return Some(current.minutes() as u16);
-            };
-            for neighbor in self.neighbors(&current) {
-                let tentative_gscore = current_gscore + 1;
-                if tentative_gscore < g_score[neighbor.index()] {
-                    let neighbor_score = tentative_gscore + self.heuristic_distance(&neighbor);
+    /// How a point inside the basin is drawn: its blizzard, or their number if there are several.
+    fn blizzard_glyph(&self, p: Point, minutes: Minutes) -> Option<char> {
+        let mut found = Vec::new();
+        if let Some((forward, backward)) = self.blizzards_by_col.get(&p.x) {
+            let h = self.height as i16;
+            let m = minutes % h;
+            found.extend(
+                forward
+                    .iter()
+                    .filter(|b| b.y_at_offset(m, h) == p.y)
+                    .map(|_| 'v'),
+            );
+            found.extend(
+                backward
+                    .iter()
+                    .filter(|b| b.y_at_offset(h - m, h) == p.y)
+                    .map(|_| '^'),
+            );
+        }
+        if let Some((forward, backward)) = self.blizzards_by_row.get(&p.y) {
+            let w = self.width as i16;
+            let m = minutes % w;
+            found.extend(
+                forward
+                    .iter()
+                    .filter(|b| b.x_at_offset(m, w) == p.x)
+                    .map(|_| '>'),
+            );
+            found.extend(
+                backward
+                    .iter()
+                    .filter(|b| b.x_at_offset(w - m, w) == p.x)
+                    .map(|_| '<'),
+            );
+        }
 
-                    g_score[neighbor.index()] = tentative_gscore;
-                    open_set.push(neighbor, Reverse(neighbor_score));
-                }
-            }
+        match found[..] {
+            [] => None,
+            [c] => Some(c),
+            _ => char::from_digit(found.len() as u32, 10),
         }
+    }
 
-        None
+    /// The basin with its walls and blizzards at the given minute and the expedition at `pos`.
+    fn view(&self, minutes: Minutes, pos: Point) -> impl Canvas + '_ {
+        let min = aoc::point::Point::new(-1, -1);
+        let max = aoc::point::Point::new(self.width as i64, self.height as i64);
+
+        FnCanvas::new(min, max, move |p| {
+            let p = Point::new(p.x as Coordinate, p.y as Coordinate);
+            if p == pos {
+                Glyph::colored('E', Color::Yellow)
+            } else if p == self.start || p == self.end {
+                Glyph::plain('.')
+            } else if p.x < 0 || p.y < 0 || p.x >= self.width || p.y >= self.height {
+                Glyph::colored('#', Color::Gray)
+            } else if let Some(c) = self.blizzard_glyph(p, minutes) {
+                Glyph::colored(c, Color::Cyan)
+            } else {
+                Glyph::plain('.')
+            }
+        })
     }
 
     fn neighbors(&self, node: &SearchState) -> impl Iterator<Item = SearchState> + '_ {
@@ -265,6 +335,19 @@ impl Basin {
             .chain(once(wait_state))
             .filter(|s| self.is_empty(s.pos, s.minutes()))
     }
+}
+
+impl Graph for Basin {
+    type Node = SearchState;
+    type Cost = u16;
+
+    fn is_solution(&self, node: &SearchState) -> bool {
+        node.pos == self.end && (!self.go_back_to_start || node.state() == VisitedStartAfterEnd)
+    }
+
+    fn collect_neighbors(&self, node: &SearchState, neighbors: &mut Vec<(SearchState, u16)>) {
+        neighbors.extend(self.neighbors(node).map(|n| (n, 1)));
+    }
 
     fn heuristic_distance(&self, node: &SearchState) -> u16 {
         let h = if self.go_back_to_start {
@@ -285,10 +368,33 @@ impl Basin {
     }
 }
 
+/// Animates the expedition along the shortest path of part two.
+fn animate(input: &str, animation: &mut Animation) -> anyhow::Result<()> {
+    let basin = Basin::parse(input, true)?;
+    let result = a_star_with_path(&basin, once(basin.start_state()), Basin::scores());
+    ensure!(result.solution.is_some(), "no path through the basin");
+
+    for state in &result.path {
+        let caption = format!("minute {}", state.minutes());
+        animation.frame(&basin.view(state.minutes(), state.pos), &caption);
+    }
+    if let Some(last) = result.path.last() {
+        let caption = format!("reached the goal after {} minutes", last.minutes());
+        animation.finish(&basin.view(last.minutes(), last.pos), &caption);
+    }
+    Ok(())
+}
+
 fn main() {
-    let input = &aoc::read_file("inputs", 24);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(24);
+    if let Some(mut animation) = Animation::from_env() {
+        if let Err(e) = animate(input, &mut animation) {
+            eprintln!("{:#}", e);
+        }
+        return;
+    }
+    aoc::solve!(24, 1, part_one, input);
+    aoc::solve!(24, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -298,12 +404,19 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", 24);
-        assert_eq!(part_one(&input), Some(18));
+        assert_eq!(part_one(&input).unwrap(), Some(18));
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", 24);
-        assert_eq!(part_two(&input), Some(54));
+        assert_eq!(part_two(&input).unwrap(), Some(54));
+    }
+
+    #[test]
+    fn test_malformed_basin() {
+        assert!(part_one("#\n#\n#").is_err());
+        let err = part_one("#.###\n#>.#\n###.#").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected width 5, got 4");
     }
 }
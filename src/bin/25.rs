@@ -47,9 +47,9 @@ fn snafu_char_to_digit(c: char) -> i64 {
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 25);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(25);
+    aoc::solve!(25, 1, part_one, input);
+    aoc::solve!(25, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -8,18 +8,18 @@ use std::{
     process,
 };
 
-const MODULE_TEMPLATE: &str = r#"pub fn part_one(input: &str) -> Option<u32> {
-    None
+const MODULE_TEMPLATE: &str = r#"pub fn part_one(input: &str) -> anyhow::Result<Option<u32>> {
+    Ok(None)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> anyhow::Result<Option<u32>> {
+    Ok(None)
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", DAY);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    let input = &aoc::input::read_input(DAY);
+    aoc::solve!(DAY, 1, part_one, input);
+    aoc::solve!(DAY, 2, part_two, input);
 }
 
 #[cfg(test)]
@@ -29,13 +29,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = aoc::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input).unwrap(), None);
     }
 
     #[test]
     fn test_part_two() {
         let input = aoc::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input).unwrap(), None);
     }
 }
 "#;
@@ -50,7 +50,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 fn main() {
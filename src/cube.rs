@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+
+use anyhow::{bail, ensure};
+use num::PrimInt;
+
+use crate::point::{CardinalDirection, Direction3, Point};
+
+/// A cube folded from a net of six square faces drawn on a map, e.g. 2022 day 22.
+///
+/// Faces are numbered in reading order of the net. Edges are described from the inside of a
+/// face, so the `E` edge of a face is its rightmost column in the net.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeNet {
+    size: i64,
+    faces: Vec<Face>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Face {
+    /// Top left corner of the face in the net, in map coordinates.
+    origin: Point<i64>,
+    /// The face across each edge and the edge of that face it is glued to, by direction index.
+    neighbors: [(usize, CardinalDirection); 4],
+}
+
+/// Where a face ended up once folded: the outward normal and the directions of the net's `x`
+/// and `y` axes on it.
+#[derive(Debug, Clone, Copy)]
+struct Orientation {
+    normal: Direction3,
+    right: Direction3,
+    down: Direction3,
+}
+
+impl Orientation {
+    /// The orientation of the face across the given edge, folding it away from the viewer.
+    fn fold(self, d: CardinalDirection) -> Orientation {
+        let Orientation {
+            normal,
+            right,
+            down,
+        } = self;
+        match d {
+            CardinalDirection::E => Orientation {
+                normal: right,
+                right: normal.opposite(),
+                down,
+            },
+            CardinalDirection::W => Orientation {
+                normal: right.opposite(),
+                right: normal,
+                down,
+            },
+            CardinalDirection::S => Orientation {
+                normal: down,
+                right,
+                down: normal.opposite(),
+            },
+            CardinalDirection::N => Orientation {
+                normal: down.opposite(),
+                right,
+                down: normal,
+            },
+        }
+    }
+
+    /// The direction in 3D of the given edge of the face.
+    fn edge(self, d: CardinalDirection) -> Direction3 {
+        match d {
+            CardinalDirection::E => self.right,
+            CardinalDirection::W => self.right.opposite(),
+            CardinalDirection::S => self.down,
+            CardinalDirection::N => self.down.opposite(),
+        }
+    }
+}
+
+impl CubeNet {
+    /// Folds the net drawn by the non-blank cells of the rows, with spaces or missing cells
+    /// outside of it. The face size is derived from the number of cells.
+    pub fn from_rows<R: AsRef<[u8]>>(rows: &[R]) -> anyhow::Result<Self> {
+        let cell = |x: i64, y: i64| {
+            rows.get(y as usize)
+                .and_then(|row| row.as_ref().get(x as usize))
+                .is_some_and(|&c| c != b' ')
+        };
+
+        let cells: usize = rows
+            .iter()
+            .map(|row| row.as_ref().iter().filter(|&&c| c != b' ').count())
+            .sum();
+        let size = (1..)
+            .map(|n| n as i64)
+            .find(|n| 6 * n * n >= cells as i64)
+            .unwrap();
+        ensure!(
+            cells > 0 && 6 * size * size == cells as i64,
+            "a cube net has 6 square faces, got {} cells",
+            cells
+        );
+
+        let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0) as i64;
+        let mut origins = Vec::new();
+        for y in (0..rows.len() as i64).step_by(size as usize) {
+            for x in (0..width).step_by(size as usize) {
+                if cell(x, y) {
+                    origins.push(Point::new(x, y));
+                }
+            }
+        }
+        CubeNet::new(size, origins)
+    }
+
+    /// Folds the net with faces of `size` cells whose top left corners are `origins`.
+    pub fn new(size: i64, origins: Vec<Point<i64>>) -> anyhow::Result<Self> {
+        ensure!(
+            origins.len() == 6,
+            "a cube net has 6 faces, got {}",
+            origins.len()
+        );
+        let face_at = |p: Point<i64>| origins.iter().position(|&o| o == p);
+
+        // walk the net from the first face, folding each face onto its neighbour
+        let mut orientations: Vec<Option<Orientation>> = vec![None; 6];
+        orientations[0] = Some(Orientation {
+            normal: Direction3::NegZ,
+            right: Direction3::PosX,
+            down: Direction3::PosY,
+        });
+        let mut queue = VecDeque::from([0]);
+        while let Some(i) = queue.pop_front() {
+            let orientation = orientations[i].unwrap();
+            for d in CardinalDirection::values() {
+                let (dx, dy) = d.deltas();
+                let next = origins[i] + Point::new(dx as i64, dy as i64) * size;
+                if let Some(j) = face_at(next) {
+                    if orientations[j].is_none() {
+                        orientations[j] = Some(orientation.fold(d));
+                        queue.push_back(j);
+                    }
+                }
+            }
+        }
+
+        let Some(orientations) = orientations.into_iter().collect::<Option<Vec<_>>>() else {
+            bail!("the faces of the cube net are not connected");
+        };
+        let face_with_normal = |normal: Direction3| {
+            orientations
+                .iter()
+                .position(|o| o.normal == normal)
+                .unwrap()
+        };
+        if Direction3::values().any(|n| !orientations.iter().any(|o| o.normal == n)) {
+            bail!("the net does not fold into a cube");
+        }
+
+        let faces = origins
+            .iter()
+            .zip(&orientations)
+            .map(|(&origin, &orientation)| {
+                let neighbors = [0, 1, 2, 3].map(|i| {
+                    let d = CardinalDirection::from_index(i);
+                    let j = face_with_normal(orientation.edge(d));
+                    let edge = CardinalDirection::values()
+                        .find(|&e| orientations[j].edge(e) == orientation.normal)
+                        .unwrap();
+                    (j, edge)
+                });
+                Face { origin, neighbors }
+            })
+            .collect();
+
+        Ok(CubeNet { size, faces })
+    }
+
+    /// Length of the side of a face, in cells.
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    /// The face a point of the map is on and its coordinates on that face.
+    pub fn to_face<T: PrimInt>(&self, p: Point<T>) -> Option<(usize, Point<i64>)> {
+        let p = Point::new(p.x.to_i64()?, p.y.to_i64()?);
+        self.faces.iter().enumerate().find_map(|(i, face)| {
+            let local = p - face.origin;
+            let inside = |v: i64| (0..self.size).contains(&v);
+            (inside(local.x) && inside(local.y)).then_some((i, local))
+        })
+    }
+
+    /// The point of the map at the given coordinates on a face.
+    ///
+    /// Panics if the point does not fit the coordinate type.
+    pub fn to_map<T: PrimInt>(&self, face: usize, local: Point<i64>) -> Point<T> {
+        let p = self.faces[face].origin + local;
+        Point::new(T::from(p.x).unwrap(), T::from(p.y).unwrap())
+    }
+
+    /// The face across the given edge of a face and the edge of that face they share.
+    pub fn neighbor(&self, face: usize, edge: CardinalDirection) -> (usize, CardinalDirection) {
+        self.faces[face].neighbors[edge.index()]
+    }
+
+    /// Moves one step from `p` towards `facing` across the edges of the cube, returning the new
+    /// point of the map and the facing once on the next face.
+    ///
+    /// Panics if `p` is not on the net.
+    pub fn step<T: PrimInt>(
+        &self,
+        p: Point<T>,
+        facing: CardinalDirection,
+    ) -> (Point<T>, CardinalDirection) {
+        let (face, local) = self.to_face(p).expect("point outside of the cube net");
+        let (dx, dy) = facing.deltas();
+        let next = local + Point::new(dx as i64, dy as i64);
+        if (0..self.size).contains(&next.x) && (0..self.size).contains(&next.y) {
+            return (self.to_map(face, next), facing);
+        }
+
+        // the shared edge is walked clockwise on one face and counterclockwise on the other
+        let offset = self.clockwise_offset(facing, local);
+        let (next_face, edge) = self.neighbor(face, facing);
+        let local = self.clockwise_cell(edge, self.size - 1 - offset);
+        (self.to_map(next_face, local), edge.opposite())
+    }
+
+    /// Position of a cell along an edge, walking around the face clockwise.
+    fn clockwise_offset(&self, edge: CardinalDirection, local: Point<i64>) -> i64 {
+        let last = self.size - 1;
+        match edge {
+            CardinalDirection::N => local.x,
+            CardinalDirection::E => local.y,
+            CardinalDirection::S => last - local.x,
+            CardinalDirection::W => last - local.y,
+        }
+    }
+
+    fn clockwise_cell(&self, edge: CardinalDirection, offset: i64) -> Point<i64> {
+        let last = self.size - 1;
+        match edge {
+            CardinalDirection::N => Point::new(offset, 0),
+            CardinalDirection::E => Point::new(last, offset),
+            CardinalDirection::S => Point::new(last - offset, last),
+            CardinalDirection::W => Point::new(0, last - offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::*;
+
+    /// The 11 nets of the cube, with `#` for faces.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "###..\n..###",
+        "##..\n.##.\n..##",
+    ];
+
+    fn scale(net: &str, size: usize) -> Vec<Vec<u8>> {
+        net.lines()
+            .flat_map(|line| {
+                let row: Vec<u8> = line
+                    .bytes()
+                    .flat_map(|c| iter::repeat_n(if c == b'#' { b'.' } else { b' ' }, size))
+                    .collect();
+                vec![row; size]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_all_nets_fold() {
+        for net in NETS {
+            for size in [1, 4] {
+                let cube = CubeNet::from_rows(&scale(net, size)).unwrap();
+                for face in 0..6 {
+                    for edge in CardinalDirection::values() {
+                        let (next, back) = cube.neighbor(face, edge);
+                        assert_ne!(next, face);
+                        assert_eq!(cube.neighbor(next, back), (face, edge), "{}", net);
+                    }
+                }
+
+                // walking four faces in any direction comes back to the same point and facing
+                for face in 0..6 {
+                    let start: Point<i32> = cube.to_map(face, Point::new(0, size as i64 - 1));
+                    for facing in CardinalDirection::values() {
+                        let (mut p, mut f) = (start, facing);
+                        for _ in 0..4 * size {
+                            (p, f) = cube.step(p, f);
+                        }
+                        assert_eq!((p, f), (start, facing), "{}", net);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_nets() {
+        assert!(CubeNet::from_rows(&scale("######", 2)).is_err());
+        assert!(CubeNet::from_rows(&scale("##\n##\n##", 2)).is_err());
+        assert!(CubeNet::from_rows(&scale("#...\n####\n....\n#...", 2)).is_err());
+        assert!(CubeNet::from_rows(&["...", ".."]).is_err());
+    }
+}
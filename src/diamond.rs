@@ -0,0 +1,225 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::RangeInclusive,
+};
+
+use num::{PrimInt, Signed};
+
+use crate::point::Point;
+
+/// Rotates a point by 45° into `(x + y, x - y)`, where manhattan distances become chebyshev
+/// distances and diamonds become axis-aligned squares.
+pub fn to_rotated<T: PrimInt>(p: Point<T>) -> Point<T> {
+    Point::new(p.x + p.y, p.x - p.y)
+}
+
+/// The inverse of [`to_rotated`], or `None` for rotated points between integer points, which
+/// have coordinates of different parity.
+pub fn from_rotated<T: PrimInt>(p: Point<T>) -> Option<Point<T>> {
+    let two = T::one() + T::one();
+    let (x, y) = ((p.x + p.y) / two, (p.x - p.y) / two);
+    (x + y == p.x && x - y == p.y).then_some(Point::new(x, y))
+}
+
+/// The points within a manhattan distance of a center, shaped like a square rotated by 45°.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diamond<T> {
+    pub center: Point<T>,
+    pub radius: T,
+}
+
+impl<T: Display> Debug for Diamond<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}±{}", self.center, self.radius)
+    }
+}
+
+impl<T: PrimInt + Signed> Diamond<T> {
+    pub fn new(center: Point<T>, radius: T) -> Self {
+        Diamond { center, radius }
+    }
+
+    /// The smallest diamond around `center` that contains `p`.
+    pub fn through(center: Point<T>, p: Point<T>) -> Self {
+        let d = (p.x - center.x).abs() + (p.y - center.y).abs();
+        Diamond::new(center, d)
+    }
+
+    pub fn contains(&self, p: Point<T>) -> bool {
+        (p.x - self.center.x).abs() + (p.y - self.center.y).abs() <= self.radius
+    }
+
+    /// The same diamond with the radius grown by `by`, e.g. 1 for the points just outside.
+    pub fn grow(&self, by: T) -> Self {
+        Diamond::new(self.center, self.radius + by)
+    }
+
+    /// The top, right, bottom and left corners.
+    pub fn corners(&self) -> [Point<T>; 4] {
+        let (c, r) = (self.center, self.radius);
+        [
+            Point::new(c.x, c.y - r),
+            Point::new(c.x + r, c.y),
+            Point::new(c.x, c.y + r),
+            Point::new(c.x - r, c.y),
+        ]
+    }
+
+    /// Inclusive corners of the square covered by the diamond in rotated coordinates.
+    pub fn rotated_bounds(&self) -> (Point<T>, Point<T>) {
+        let c = to_rotated(self.center);
+        let r = self.radius;
+        (Point::new(c.x - r, c.y - r), Point::new(c.x + r, c.y + r))
+    }
+
+    /// Whether the diamonds share at least one point.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The overlap of two diamonds, which is a rectangle in rotated coordinates, as its
+    /// inclusive corners. Only its points accepted by [`from_rotated`] are integer points.
+    pub fn intersection(&self, other: &Self) -> Option<(Point<T>, Point<T>)> {
+        let (a_min, a_max) = self.rotated_bounds();
+        let (b_min, b_max) = other.rotated_bounds();
+        let min = Point::new(a_min.x.max(b_min.x), a_min.y.max(b_min.y));
+        let max = Point::new(a_max.x.min(b_max.x), a_max.y.min(b_max.y));
+        (min.x <= max.x && min.y <= max.y).then_some((min, max))
+    }
+
+    /// The points of the diamond on row `y`.
+    pub fn clip_row(&self, y: T) -> Option<RangeInclusive<T>> {
+        let w = self.radius - (y - self.center.y).abs();
+        (w >= T::zero()).then(|| self.center.x - w..=self.center.x + w)
+    }
+
+    /// The points of the diamond on column `x`.
+    pub fn clip_column(&self, x: T) -> Option<RangeInclusive<T>> {
+        let h = self.radius - (x - self.center.x).abs();
+        (h >= T::zero()).then(|| self.center.y - h..=self.center.y + h)
+    }
+
+    /// Whether every point of the rectangle between the inclusive corners is in the diamond.
+    pub fn covers_rect(&self, min: Point<T>, max: Point<T>) -> bool {
+        // diamonds are convex, so covering the corners is enough
+        [min, Point::new(max.x, min.y), Point::new(min.x, max.y), max]
+            .into_iter()
+            .all(|p| self.contains(p))
+    }
+
+    /// Whether any point of the rectangle between the inclusive corners is in the diamond.
+    pub fn overlaps_rect(&self, min: Point<T>, max: Point<T>) -> bool {
+        let closest = Point::new(
+            self.center.x.max(min.x).min(max.x),
+            self.center.y.max(min.y).min(max.y),
+        );
+        self.contains(closest)
+    }
+}
+
+/// A point of the rectangle between the inclusive corners that none of the diamonds contain.
+///
+/// Splits the rectangle in quarters until each part is either covered by one diamond or out of
+/// reach of all of them, so it is fast when the uncovered area is small or large.
+pub fn find_uncovered<T: PrimInt + Signed>(
+    diamonds: &[Diamond<T>],
+    min: Point<T>,
+    max: Point<T>,
+) -> Option<Point<T>> {
+    if min.x > max.x || min.y > max.y {
+        return None;
+    }
+    let near: Vec<_> = diamonds
+        .iter()
+        .filter(|d| d.overlaps_rect(min, max))
+        .copied()
+        .collect();
+    if near.is_empty() {
+        return Some(min);
+    }
+    if min == max || near.iter().any(|d| d.covers_rect(min, max)) {
+        return None;
+    }
+
+    let two = T::one() + T::one();
+    let mid = Point::new(min.x + (max.x - min.x) / two, min.y + (max.y - min.y) / two);
+    let one = T::one();
+    [
+        (min, mid),
+        (Point::new(mid.x + one, min.y), Point::new(max.x, mid.y)),
+        (Point::new(min.x, mid.y + one), Point::new(mid.x, max.y)),
+        (Point::new(mid.x + one, mid.y + one), max),
+    ]
+    .into_iter()
+    .find_map(|(min, max)| find_uncovered(&near, min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_and_clipping() {
+        let p = Point::new(3i32, -5);
+        assert_eq!(to_rotated(p), Point::new(-2, 8));
+        assert_eq!(from_rotated(to_rotated(p)), Some(p));
+        assert_eq!(from_rotated(Point::new(1i32, 2)), None);
+
+        let d = Diamond::through(Point::new(8i32, 7), Point::new(2, 10));
+        assert_eq!(d.radius, 9);
+        assert_eq!(d.clip_row(10), Some(2..=14));
+        assert_eq!(d.clip_row(16), Some(8..=8));
+        assert_eq!(d.clip_row(17), None);
+        assert_eq!(d.clip_column(-1), Some(7..=7));
+        assert!(d
+            .corners()
+            .iter()
+            .all(|&c| d.contains(c) && !d.grow(-1).contains(c)));
+
+        let (min, max) = d.rotated_bounds();
+        for y in -5..20 {
+            for x in -5..20 {
+                let r = to_rotated(Point::new(x, y));
+                let inside = (min.x..=max.x).contains(&r.x) && (min.y..=max.y).contains(&r.y);
+                assert_eq!(inside, d.contains(Point::new(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersections_and_coverage() {
+        let a = Diamond::new(Point::new(0i64, 0), 2);
+        let b = Diamond::new(Point::new(4, 0), 2);
+        let c = Diamond::new(Point::new(3, 2), 1);
+        assert_eq!(
+            a.intersection(&b),
+            Some((Point::new(2, 2), Point::new(2, 2)))
+        );
+        assert!(a.intersects(&b) && !a.intersects(&c));
+        assert_eq!(from_rotated(Point::new(2, 2)), Some(Point::new(2, 0)));
+        let (min, max) = a.intersection(&Diamond::new(Point::new(3, 0), 2)).unwrap();
+        assert_eq!((min, max), (Point::new(1, 1), Point::new(2, 2)));
+        assert_eq!(from_rotated(min), Some(Point::new(1, 0)));
+
+        assert!(a.covers_rect(Point::new(-1, 0), Point::new(1, 1)));
+        assert!(!a.covers_rect(Point::new(-1, -1), Point::new(2, 1)));
+        assert!(a.overlaps_rect(Point::new(2, 0), Point::new(5, 5)));
+        assert!(!a.overlaps_rect(Point::new(2, 1), Point::new(5, 5)));
+
+        let diamonds = [
+            a,
+            b,
+            c,
+            Diamond::new(Point::new(2, 4), 2),
+            Diamond::new(Point::new(2, 1), 0),
+            Diamond::new(Point::new(1, 3), 1),
+        ];
+        let hole = find_uncovered(&diamonds, Point::new(0, 0), Point::new(4, 4));
+        assert_eq!(hole, Some(Point::new(4, 3)));
+        let patched = [&diamonds[..], &[Diamond::new(Point::new(4, 3), 0)]].concat();
+        assert_eq!(
+            find_uncovered(&patched, Point::new(0, 0), Point::new(4, 4)),
+            None
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::{fs, path::Path, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use hashbrown::HashMap;
+
+use crate::{
+    params::Params,
+    record::{Answer, Outcome},
+};
+
+/// Line separating the header of an example file from its input.
+pub const HEADER_END: &str = "---";
+
+/// A named example case from `src/examples/NN/<name>.txt`.
+///
+/// The file may start with a header of `key: value` lines terminated by a [`HEADER_END`] line.
+/// The keys `part_one` and `part_two` hold the expected answers, all other keys are
+/// parameters for the solver:
+///
+/// ```text
+/// part_one: 26
+/// part_two: 56000011
+/// y: 10
+/// ---
+/// Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    pub params: HashMap<String, String>,
+    pub input: String,
+}
+
+impl Example {
+    pub fn parse(name: &str, s: &str) -> anyhow::Result<Example> {
+        let mut example = Example {
+            name: name.to_owned(),
+            part_one: None,
+            part_two: None,
+            params: HashMap::new(),
+            input: s.to_owned(),
+        };
+
+        let (header, input) = match s.split_once(&format!("\n{}\n", HEADER_END)) {
+            Some(parts) => parts,
+            None => return Ok(example),
+        };
+
+        for (i, line) in header.lines().enumerate() {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow!("line {}: expected 'key: value', got '{}'", i + 1, line))?;
+            let value = value.trim().to_owned();
+            match key.trim() {
+                "part_one" => example.part_one = Some(value),
+                "part_two" => example.part_two = Some(value),
+                key => {
+                    example.params.insert(key.to_owned(), value);
+                }
+            }
+        }
+
+        example.input = input.to_owned();
+        Ok(example)
+    }
+
+    /// Parses the solver parameter `key` given in the header.
+    pub fn param<T>(&self, key: &str) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        let value = self
+            .params
+            .get(key)
+            .ok_or_else(|| anyhow!("example '{}' has no parameter '{}'", self.name, key))?;
+        value
+            .parse()
+            .map_err(Into::into)
+            .with_context(|| format!("invalid parameter '{}' in example '{}'", key, self.name))
+    }
+
+    /// The day's parameters with the values from the header applied.
+    pub fn parse_params<P: Params>(&self) -> anyhow::Result<P> {
+        P::with_overrides(self.params.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .with_context(|| format!("invalid parameters in example '{}'", self.name))
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            _ => self.part_two.as_deref(),
+        }
+    }
+}
+
+/// Reads the example case `name` of the given day.
+pub fn read_example(day: u8, name: &str) -> anyhow::Result<Example> {
+    let path = crate::repo_file(
+        Path::new("src")
+            .join("examples")
+            .join(format!("{:02}", day))
+            .join(format!("{}.txt", name)),
+    )?;
+    let s = fs::read_to_string(&path)
+        .with_context(|| format!("could not open example '{}'", path.display()))?;
+    Example::parse(name, &s).with_context(|| format!("invalid example '{}'", path.display()))
+}
+
+/// Runs the solvers on an example case and panics unless they give the expected answers.
+///
+/// Only the parts with an expected answer in the header are run, so that a case can cover just
+/// one of the parts.
+pub fn check_case<R1: Answer, R2: Answer>(
+    day: u8,
+    name: &str,
+    part_one: impl Fn(&str, &Example) -> R1,
+    part_two: impl Fn(&str, &Example) -> R2,
+) {
+    let example = read_example(day, name).unwrap_or_else(|e| panic!("{:#}", e));
+    if example.part_one.is_none() && example.part_two.is_none() {
+        panic!("example '{}' has no expected answers", name);
+    }
+
+    if let Some(expected) = example.expected(1) {
+        let outcome = part_one(&example.input, &example).into_outcome();
+        check_outcome(name, 1, expected, outcome).unwrap_or_else(|e| panic!("{:#}", e));
+    }
+    if let Some(expected) = example.expected(2) {
+        let outcome = part_two(&example.input, &example).into_outcome();
+        check_outcome(name, 2, expected, outcome).unwrap_or_else(|e| panic!("{:#}", e));
+    }
+}
+
+fn check_outcome(name: &str, part: u8, expected: &str, outcome: Outcome) -> anyhow::Result<()> {
+    match outcome {
+        Outcome::Solved(answer) if answer.trim() == expected => Ok(()),
+        Outcome::Solved(answer) => bail!(
+            "example '{}' part {}: expected {}, got {}",
+            name,
+            part,
+            expected,
+            answer
+        ),
+        Outcome::NotSolved => bail!("example '{}' part {}: not solved", name, part),
+        Outcome::Failed(e) => Err(e.context(format!("example '{}' part {}", name, part))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_example_with_header() {
+        let example = Example::parse("small", "part_one: 26\ny: 10\n---\n1\n2\n").unwrap();
+        assert_eq!(example.part_one.as_deref(), Some("26"));
+        assert_eq!(example.part_two, None);
+        assert_eq!(example.param::<i32>("y").unwrap(), 10);
+        assert!(example.param::<i32>("x").is_err());
+        assert_eq!(example.input, "1\n2\n");
+    }
+
+    #[test]
+    fn test_parse_example_without_header() {
+        let example = Example::parse("plain", "1\n2\n").unwrap();
+        assert_eq!(example.expected(1), None);
+        assert_eq!(example.input, "1\n2\n");
+    }
+}
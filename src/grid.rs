@@ -0,0 +1,546 @@
+use std::{
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context};
+use num::PrimInt;
+
+use crate::point::{CardinalDirection, CompassDirection, Point};
+
+/// A rectangular grid of cells stored row by row in a flat `Vec`.
+///
+/// Cells are addressed with points of any integer coordinate type, where `x` is the column
+/// and `y` the row. Points outside of the grid are never returned by the neighbour iterators,
+/// and indexing with them panics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Cells that can be parsed from a single character of a map.
+pub trait Cell: Sized {
+    fn from_char(c: char) -> anyhow::Result<Self>;
+}
+
+impl Cell for char {
+    fn from_char(c: char) -> anyhow::Result<Self> {
+        Ok(c)
+    }
+}
+
+impl Cell for u8 {
+    fn from_char(c: char) -> anyhow::Result<Self> {
+        u8::try_from(c).map_err(|_| anyhow!("non-ASCII cell '{}'", c))
+    }
+}
+
+impl<T: Cell> FromStr for Grid<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, T::from_char)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {}",
+            cells.len(),
+            width
+        );
+        Grid {
+            height: cells.len() / width,
+            cells,
+            width,
+        }
+    }
+
+    /// Parses a map with one row per line, converting each character with `f`.
+    pub fn parse_with(
+        s: &str,
+        mut f: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in s.lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).with_context(|| format!("line {}, column {}", y + 1, x + 1))?);
+            }
+
+            let line_width = cells.len() - before;
+            if y == 0 {
+                width = line_width;
+            } else if line_width != width {
+                bail!(
+                    "line {}: expected {} cells, got {}",
+                    y + 1,
+                    width,
+                    line_width
+                );
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Like [`Grid::parse_with`], but also returns the position of each of the `markers`, e.g.
+    /// the start and end of a maze. Each marker has to occur exactly once.
+    pub fn parse_with_markers<C: PrimInt, const N: usize>(
+        s: &str,
+        markers: [char; N],
+        mut f: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<(Self, [Point<C>; N])> {
+        let mut found = [None; N];
+        let mut index = 0;
+
+        let grid = Grid::parse_with(s, |c| {
+            if let Some(m) = markers.iter().position(|&m| m == c) {
+                if found[m].replace(index).is_some() {
+                    bail!("marker '{}' occurs more than once", c);
+                }
+            }
+            index += 1;
+            f(c)
+        })?;
+
+        let mut points = [Point::new(C::zero(), C::zero()); N];
+        for (i, marker) in markers.iter().enumerate() {
+            let index = found[i].ok_or_else(|| anyhow!("marker '{}' not found", marker))?;
+            points[i] = grid
+                .point_of(index)
+                .ok_or_else(|| anyhow!("marker '{}' is out of range", marker))?;
+        }
+        Ok((grid, points))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Index of `p` in the flat cell vector, or `None` if it is outside of the grid.
+    #[inline]
+    pub fn index_of<C: PrimInt>(&self, p: Point<C>) -> Option<usize> {
+        let x = p.x.to_usize()?;
+        let y = p.y.to_usize()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn point_of<C: PrimInt>(&self, index: usize) -> Option<Point<C>> {
+        if index >= self.cells.len() {
+            return None;
+        }
+        Some(Point::new(
+            C::from(index % self.width)?,
+            C::from(index / self.width)?,
+        ))
+    }
+
+    #[inline]
+    pub fn contains<C: PrimInt>(&self, p: Point<C>) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    pub fn get<C: PrimInt>(&self, p: Point<C>) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<C: PrimInt>(&mut self, p: Point<C>) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} out of range", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// All points of the grid in row-major order.
+    pub fn points<C: PrimInt>(&self) -> impl Iterator<Item = Point<C>> + '_ {
+        (0..self.cells.len()).filter_map(|i| self.point_of(i))
+    }
+
+    /// Position of the first cell matching `predicate` in row-major order.
+    pub fn position<C: PrimInt>(&self, predicate: impl Fn(&T) -> bool) -> Option<Point<C>> {
+        self.point_of(self.cells.iter().position(predicate)?)
+    }
+
+    /// The point next to `p` in the direction `(dx, dy)`, if it is inside of the grid.
+    pub fn offset<C: PrimInt>(&self, p: Point<C>, (dx, dy): (i32, i32)) -> Option<Point<C>> {
+        let x = p.x.to_i64()? + dx as i64;
+        let y = p.y.to_i64()? + dy as i64;
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        }
+        Some(Point::new(C::from(x)?, C::from(y)?))
+    }
+
+    /// The up to four horizontally and vertically adjacent points inside of the grid.
+    pub fn neighbors4<'a, C: PrimInt + 'a>(
+        &'a self,
+        p: Point<C>,
+    ) -> impl Iterator<Item = Point<C>> + 'a {
+        CardinalDirection::values().filter_map(move |d| self.offset(p, d.deltas()))
+    }
+
+    /// The up to eight adjacent points inside of the grid, including diagonals.
+    pub fn neighbors8<'a, C: PrimInt + 'a>(
+        &'a self,
+        p: Point<C>,
+    ) -> impl Iterator<Item = Point<C>> + 'a {
+        CompassDirection::values().filter_map(move |d| self.offset(p, d.deltas()))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T, C: PrimInt> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, p: Point<C>) -> &T {
+        match self.index_of(p) {
+            Some(i) => &self.cells[i],
+            None => panic!("point outside of {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T, C: PrimInt> IndexMut<Point<C>> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, p: Point<C>) -> &mut T {
+        match self.index_of(p) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("point outside of {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+/// Cells added beyond the written point when an [`InfiniteGrid`] grows.
+const GROWTH_CHUNK: i64 = 16;
+
+/// A grid without fixed bounds for simulations whose extent is not known up front.
+///
+/// Writing a cell outside of the allocated area grows the grid in that direction, at least
+/// doubling the grown dimension so that copying amortises. Cells that were never written read
+/// as the default value, and all access is O(1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfiniteGrid<T> {
+    cells: Vec<T>,
+    default: T,
+    min_x: i64,
+    min_y: i64,
+    width: usize,
+    height: usize,
+    bounds: Option<(Point<i64>, Point<i64>)>,
+}
+
+impl<T: Clone + Default> Default for InfiniteGrid<T> {
+    fn default() -> Self {
+        InfiniteGrid::with_default(T::default())
+    }
+}
+
+impl<T: Clone + Default> InfiniteGrid<T> {
+    pub fn new() -> Self {
+        InfiniteGrid::default()
+    }
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    /// Creates an empty grid where unwritten cells read as `default`.
+    pub fn with_default(default: T) -> Self {
+        InfiniteGrid {
+            cells: Vec::new(),
+            default,
+            min_x: 0,
+            min_y: 0,
+            width: 0,
+            height: 0,
+            bounds: None,
+        }
+    }
+
+    /// Width of the allocated area, i.e. the distance between vertically adjacent cells in
+    /// [`InfiniteGrid::cells`].
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The allocated cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Index of `p` in [`InfiniteGrid::cells`], or `None` if it is outside of the allocated area.
+    ///
+    /// Indices are invalidated when the grid grows.
+    #[inline]
+    pub fn index_of<C: PrimInt>(&self, p: Point<C>) -> Option<usize> {
+        // Negative offsets wrap around to large values, so one comparison per axis suffices.
+        let x = p.x.to_i64()?.wrapping_sub(self.min_x) as u64;
+        let y = p.y.to_i64()?.wrapping_sub(self.min_y) as u64;
+        if x >= self.width as u64 || y >= self.height as u64 {
+            return None;
+        }
+        Some(y as usize * self.width + x as usize)
+    }
+
+    #[inline]
+    pub fn get<C: PrimInt>(&self, p: Point<C>) -> &T {
+        match self.index_of(p) {
+            Some(i) => &self.cells[i],
+            None => &self.default,
+        }
+    }
+
+    /// Returns the cell at `p`, growing the grid if needed.
+    ///
+    /// The cell counts as written for [`InfiniteGrid::bounds`] even if it is only read, so use
+    /// [`InfiniteGrid::get`] for reads.
+    #[inline]
+    pub fn get_mut<C: PrimInt>(&mut self, p: Point<C>) -> &mut T {
+        let i = match self.index_of(p) {
+            Some(i) => i,
+            None => self.grow_to(p),
+        };
+        self.extend_bounds(p);
+        &mut self.cells[i]
+    }
+
+    #[inline]
+    pub fn set<C: PrimInt>(&mut self, p: Point<C>, value: T) {
+        *self.get_mut(p) = value;
+    }
+
+    /// Grows the allocated area to include `p` without writing to it.
+    #[inline]
+    pub fn reserve<C: PrimInt>(&mut self, p: Point<C>) {
+        if self.index_of(p).is_none() {
+            self.grow_to(p);
+        }
+    }
+
+    /// Smallest and largest corner of the cells written so far. Every cell borrowed mutably
+    /// through [`InfiniteGrid::get_mut`] or `IndexMut` counts, even if it was left unchanged.
+    pub fn bounds<C: PrimInt>(&self) -> Option<(Point<C>, Point<C>)> {
+        let (min, max) = self.bounds?;
+        Some((
+            Point::new(C::from(min.x)?, C::from(min.y)?),
+            Point::new(C::from(max.x)?, C::from(max.y)?),
+        ))
+    }
+
+    /// Resets every cell to the default value, keeping the allocated area.
+    pub fn clear(&mut self) {
+        self.cells.fill(self.default.clone());
+        self.bounds = None;
+    }
+
+    #[inline]
+    fn extend_bounds<C: PrimInt>(&mut self, p: Point<C>) {
+        let p = Point::new(p.x.to_i64().unwrap(), p.y.to_i64().unwrap());
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+        });
+    }
+
+    #[cold]
+    fn grow_to<C: PrimInt>(&mut self, p: Point<C>) -> usize {
+        let x = p.x.to_i64().expect("coordinate out of range");
+        let y = p.y.to_i64().expect("coordinate out of range");
+
+        let (min_x, max_x) = grown_range(self.min_x, self.width as i64, x);
+        let (min_y, max_y) = grown_range(self.min_y, self.height as i64, y);
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+
+        let mut cells = vec![self.default.clone(); width * height];
+        for (row, old) in self.cells.chunks(self.width.max(1)).enumerate() {
+            let start =
+                (self.min_y + row as i64 - min_y) as usize * width + (self.min_x - min_x) as usize;
+            cells[start..start + old.len()].clone_from_slice(old);
+        }
+
+        self.cells = cells;
+        self.min_x = min_x;
+        self.min_y = min_y;
+        self.width = width;
+        self.height = height;
+        self.index_of(p).unwrap()
+    }
+}
+
+/// The range `min..min + len` grown to include `v`.
+fn grown_range(min: i64, len: i64, v: i64) -> (i64, i64) {
+    if len == 0 {
+        return (v - GROWTH_CHUNK, v + GROWTH_CHUNK);
+    }
+
+    let max = min + len - 1;
+    if v < min {
+        ((v - GROWTH_CHUNK).min(min - len), max)
+    } else if v > max {
+        (min, (v + GROWTH_CHUNK).max(max + len))
+    } else {
+        (min, max)
+    }
+}
+
+impl<T: Clone, C: PrimInt> Index<Point<C>> for InfiniteGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, p: Point<C>) -> &T {
+        self.get(p)
+    }
+}
+
+impl<T: Clone, C: PrimInt> IndexMut<Point<C>> for InfiniteGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, p: Point<C>) -> &mut T {
+        self.get_mut(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "Sab\ncdE\n";
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::<i32>::new(2, 0)], 'b');
+        assert_eq!(grid.row(1), &['c', 'd', 'E']);
+        assert_eq!(grid.column(1).collect::<String>(), "ad");
+        assert_eq!(grid.get(Point::<i16>::new(-1, 0)), None);
+        assert!(!grid.contains(Point::<i16>::new(3, 1)));
+        assert_eq!(grid.position(|&c| c == 'E'), Some(Point::<i16>::new(2, 1)));
+
+        assert!("ab\nc\n".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let (grid, [start, end]) = Grid::<u8>::parse_with_markers::<i16, 2>(MAP, ['S', 'E'], |c| {
+            Ok(match c {
+                'S' => 0,
+                'E' => 25,
+                _ => c as u8 - b'a',
+            })
+        })
+        .unwrap();
+        assert_eq!(start, Point::new(0, 0));
+        assert_eq!(end, Point::new(2, 1));
+        assert_eq!(grid[end], 25);
+
+        let missing = Grid::<char>::parse_with_markers::<i16, 1>("ab\n", ['S'], Ok);
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+
+        let corner: Vec<_> = grid.neighbors4(Point::<i16>::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(0, 1), Point::new(1, 0)]);
+
+        assert_eq!(grid.neighbors4(Point::<i16>::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbors8(Point::<i16>::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_infinite_grid_grows() {
+        let mut grid = InfiniteGrid::<u8>::new();
+        assert_eq!(grid.bounds::<i32>(), None);
+        assert_eq!(grid[Point::new(5, 5)], 0);
+
+        grid.set(Point::new(0, 0), 1);
+        grid.set(Point::new(-100, 3), 2);
+        grid.set(Point::new(40, -70), 3);
+        grid[Point::new(0, 500)] += 4;
+
+        assert_eq!(grid[Point::new(0, 0)], 1);
+        assert_eq!(grid[Point::new(-100, 3)], 2);
+        assert_eq!(grid[Point::new(40, -70)], 3);
+        assert_eq!(grid[Point::new(0, 500)], 4);
+        assert_eq!(grid[Point::new(1, 0)], 0);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point::<i16>::new(-100, -70), Point::new(40, 500)))
+        );
+
+        grid.reserve(Point::new(1000, 0));
+        let i = grid.index_of(Point::new(0, 0)).unwrap();
+        assert_eq!(grid.cells()[i + grid.width()], 0);
+        assert_eq!(grid.bounds::<i16>().unwrap().1.x, 40);
+
+        grid.clear();
+        assert_eq!(grid[Point::new(0, 0)], 0);
+        assert_eq!(grid.bounds::<i16>(), None);
+    }
+}
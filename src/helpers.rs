@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use anyhow::Context;
+
 pub fn parse_lines<T>(input: &str) -> impl Iterator<Item = T> + '_
 where
     T: FromStr<Err = anyhow::Error>,
@@ -7,6 +9,34 @@ where
     input.lines().map(|s| s.parse::<T>().unwrap())
 }
 
+/// Parses every line of the input, reporting the (1-based) line number of the first failure.
+pub fn try_parse_lines<T>(input: &str) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr<Err = anyhow::Error>,
+{
+    try_map_lines(input, str::parse)
+}
+
+/// Maps every line of the input with `f`, reporting the (1-based) line number of the first failure.
+pub fn try_map_lines<T>(
+    input: &str,
+    f: impl FnMut(&str) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    try_lines(input, f).collect()
+}
+
+/// Lazily maps every line of the input with `f`, adding the (1-based) line number to errors.
+/// Collect or sum into a `Result` to stop at the first failure without an intermediate `Vec`.
+pub fn try_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&str) -> anyhow::Result<T> + 'a,
+) -> impl Iterator<Item = anyhow::Result<T>> + 'a {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(i, line)| f(line).with_context(|| format!("line {}", i + 1)))
+}
+
 pub fn mut_refs<T>(vs: &mut [T], i: usize, j: usize) -> (&mut T, &mut T) {
     assert_ne!(i, j);
 
@@ -0,0 +1,417 @@
+use std::{
+    fmt::{self, Display, Write as _},
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    point::Point,
+    render::{Canvas, Color},
+};
+
+/// Background of exported images, and the colour of plain `.` and space glyphs.
+pub const BACKGROUND: [u8; 3] = [15, 15, 35];
+
+/// Colour of other plain glyphs in exported images.
+pub const FOREGROUND: [u8; 3] = [204, 204, 204];
+
+/// An RGB raster image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: [u8; 3]) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Draws every cell of the canvas as a `scale` by `scale` square.
+    ///
+    /// Glyphs are drawn in their colour; plain ones use [`FOREGROUND`], except for `.` and
+    /// spaces, which are left as [`BACKGROUND`].
+    pub fn from_canvas(canvas: &impl Canvas, scale: usize) -> Image {
+        let (min, max) = match canvas.extent() {
+            Some(extent) => extent,
+            None => return Image::new(0, 0, BACKGROUND),
+        };
+
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut image = Image::new(width * scale, height * scale, BACKGROUND);
+
+        for (y, x) in itertools::iproduct!(0..height, 0..width) {
+            let glyph = canvas.glyph(Point::new(min.x + x as i64, min.y + y as i64));
+            let color = match (glyph.color, glyph.symbol) {
+                (Some(color), _) => color.rgb(),
+                (None, '.' | ' ') => continue,
+                (None, _) => FOREGROUND,
+            };
+            image.fill_rect(x * scale, y * scale, scale, scale, color);
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<[u8; 3]> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Sets a pixel, ignoring coordinates outside of the image.
+    pub fn set(&mut self, x: usize, y: usize, color: [u8; 3]) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: [u8; 3]) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                self.pixels[y * self.width + x] = color;
+            }
+        }
+    }
+
+    /// Writes the image as a binary PPM, which needs no encoder and most viewers can open.
+    pub fn write_ppm(&self, mut out: impl Write) -> anyhow::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(self.pixels.as_flattened())?;
+        Ok(())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> anyhow::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        Ok(())
+    }
+
+    /// Writes the image in the format given by the extension of `path`, `png` or `ppm`.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !matches!(extension, "png" | "ppm") {
+            bail!("unsupported image format '{}'", path.display());
+        }
+
+        let file = File::create(path)
+            .with_context(|| format!("could not create image '{}'", path.display()))?;
+        let out = BufWriter::new(file);
+        match extension {
+            "png" => self.write_png(out),
+            _ => self.write_ppm(out),
+        }
+        .with_context(|| format!("could not write image '{}'", path.display()))
+    }
+}
+
+/// A vector image in user coordinates, for point sets that do not fit a raster.
+///
+/// The view box is fitted to the given corners, so shapes can use puzzle coordinates directly
+/// even when they are in the millions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    min: Point<f64>,
+    max: Point<f64>,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    pub fn new(min: Point<f64>, max: Point<f64>) -> Svg {
+        Svg {
+            min,
+            max,
+            elements: Vec::new(),
+        }
+    }
+
+    /// A stroke width that is about one pixel of a 1000 pixel wide image.
+    pub fn hairline(&self) -> f64 {
+        (self.max.x - self.min.x).max(self.max.y - self.min.y) / 1000.0
+    }
+
+    pub fn rect(&mut self, min: Point<f64>, width: f64, height: f64, fill: Color) {
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            min.x,
+            min.y,
+            width,
+            height,
+            fill.hex()
+        ));
+    }
+
+    pub fn circle(&mut self, center: Point<f64>, radius: f64, fill: Color) {
+        self.elements.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            center.x,
+            center.y,
+            radius,
+            fill.hex()
+        ));
+    }
+
+    pub fn line(&mut self, from: Point<f64>, to: Point<f64>, stroke: Color) {
+        self.elements.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            stroke.hex(),
+            self.hairline()
+        ));
+    }
+
+    /// A closed polygon, filled translucently so that overlaps stay visible.
+    pub fn polygon(&mut self, points: &[Point<f64>], color: Color) {
+        let mut coords = String::new();
+        for p in points {
+            let _ = write!(coords, "{},{} ", p.x, p.y);
+        }
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="{}" fill-opacity="0.3" stroke="{}" stroke-width="{}"/>"#,
+            coords.trim_end(),
+            color.hex(),
+            color.hex(),
+            self.hairline()
+        ));
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("could not write image '{}'", path.display()))
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height) = (self.max.x - self.min.x, self.max.y - self.min.y);
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            self.min.x, self.min.y, width, height
+        )?;
+        writeln!(
+            f,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            self.min.x,
+            self.min.y,
+            width,
+            height,
+            Color::Black.hex()
+        )?;
+        for element in &self.elements {
+            writeln!(f, "{}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+/// Writes numbered frames of a simulation to a directory, e.g. to turn them into a GIF with
+/// `ffmpeg -i frame_%05d.png out.gif`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameWriter {
+    pub dir: PathBuf,
+    /// Pixels per cell of raster frames.
+    pub scale: usize,
+    /// Only every `every`th frame is written.
+    pub every: u32,
+    frame: u64,
+    written: usize,
+    /// Whether the last frame was skipped, so [`FrameWriter::finish`] still has to write it.
+    pending: bool,
+}
+
+impl FrameWriter {
+    /// Creates the directory if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> anyhow::Result<FrameWriter> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("could not create frame directory '{}'", dir.display()))?;
+        Ok(FrameWriter {
+            dir,
+            scale: 4,
+            every: 1,
+            frame: 0,
+            written: 0,
+            pending: true,
+        })
+    }
+
+    /// Reads the frame settings from the command line.
+    ///
+    /// Exits the process with an error message if the directory cannot be created.
+    pub fn from_env() -> Option<FrameWriter> {
+        FrameWriter::from_env_every(1)
+    }
+
+    /// Like [`FrameWriter::from_env`], writing every `default_every`th frame unless
+    /// `--frame-every` is given, for simulations with many steps.
+    pub fn from_env_every(default_every: u32) -> Option<FrameWriter> {
+        let mut args = pico_args::Arguments::from_env();
+        FrameWriter::from_args(&mut args, default_every).unwrap_or_else(|e| {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        })
+    }
+
+    /// Consumes `--frames <dir>`, `--frame-scale <pixels>` and `--frame-every <n>` from `args`.
+    pub fn from_args(
+        args: &mut pico_args::Arguments,
+        default_every: u32,
+    ) -> anyhow::Result<Option<FrameWriter>> {
+        let (dir, scale, every) = FrameWriter::parse_args(args)?;
+        let dir = match dir {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+        let mut writer = FrameWriter::new(dir)?;
+        writer.scale = scale.unwrap_or(writer.scale).max(1);
+        writer.every = every.unwrap_or(default_every).max(1);
+        Ok(Some(writer))
+    }
+
+    /// Consumes the frame arguments without creating the directory.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn parse_args(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Option<PathBuf>, Option<usize>, Option<u32>), pico_args::Error> {
+        Ok((
+            args.opt_value_from_str("--frames")?,
+            args.opt_value_from_str("--frame-scale")?,
+            args.opt_value_from_str("--frame-every")?,
+        ))
+    }
+
+    /// Number of frames written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Writes the canvas as the next PNG frame, unless it is skipped according to
+    /// [`FrameWriter::every`].
+    pub fn frame(&mut self, canvas: &impl Canvas) -> anyhow::Result<()> {
+        if self.skip() {
+            return Ok(());
+        }
+        self.write(canvas)
+    }
+
+    /// Writes the last state of the simulation, which was passed to the last call of
+    /// [`FrameWriter::frame`], unless that call already wrote it.
+    pub fn finish(&mut self, canvas: &impl Canvas) -> anyhow::Result<()> {
+        if !self.pending {
+            return Ok(());
+        }
+        self.write(canvas)
+    }
+
+    fn write(&mut self, canvas: &impl Canvas) -> anyhow::Result<()> {
+        let image = Image::from_canvas(canvas, self.scale);
+        image.save(&self.next_path("png"))
+    }
+
+    /// Writes the next SVG frame, unless it is skipped according to [`FrameWriter::every`].
+    pub fn svg_frame(&mut self, svg: &Svg) -> anyhow::Result<()> {
+        if self.skip() {
+            return Ok(());
+        }
+        svg.save(&self.next_path("svg"))
+    }
+
+    fn skip(&mut self) -> bool {
+        self.frame += 1;
+        self.pending = !(self.frame - 1).is_multiple_of(self.every as u64);
+        self.pending
+    }
+
+    fn next_path(&mut self, extension: &str) -> PathBuf {
+        let path = self
+            .dir
+            .join(format!("frame_{:05}.{}", self.written, extension));
+        self.written += 1;
+        self.pending = false;
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn test_canvas_to_ppm_and_png() {
+        let grid: Grid<char> = "#.\n..".parse().unwrap();
+        let image = Image::from_canvas(&grid, 2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get(1, 1), Some(FOREGROUND));
+        assert_eq!(image.get(2, 1), Some(BACKGROUND));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buf).unwrap();
+        assert_eq!(&buf[..], image.pixels.as_flattened());
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new(Point::new(0.0, 0.0), Point::new(20.0, 10.0));
+        svg.polygon(
+            &[
+                Point::new(1.0, 0.0),
+                Point::new(2.0, 1.0),
+                Point::new(1.0, 2.0),
+            ],
+            Color::Green,
+        );
+        let s = svg.to_string();
+        assert!(s.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 10">"#));
+        assert!(s.contains(r#"points="1,0 2,1 1,2""#));
+        assert!(s.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_frame_writer_finish() {
+        let grid: Grid<char> = "#.\n..".parse().unwrap();
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", process::id()));
+        let mut frames = FrameWriter::new(&dir).unwrap();
+        frames.every = 2;
+
+        frames.frame(&grid).unwrap();
+        frames.finish(&grid).unwrap();
+        assert_eq!(frames.written(), 1);
+        frames.frame(&grid).unwrap();
+        frames.finish(&grid).unwrap();
+        assert_eq!(frames.written(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    bench::BenchConfig,
+    examples::read_example,
+    image::FrameWriter,
+    params::{parse_override, Params},
+    render::Animation,
+};
+
+/// Where a day binary reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `src/inputs/NN.txt`.
+    Default,
+    /// `src/examples/NN.txt` or the case `src/examples/NN/example.txt`, selected with `--example`.
+    Example,
+    /// Any file, selected with `--input <path>`.
+    Path(PathBuf),
+    /// Standard input, selected with `--input -` or a lone `-`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the input source from the command line.
+    pub fn from_env() -> Result<InputSource, pico_args::Error> {
+        InputSource::from_args(&mut pico_args::Arguments::from_env())
+    }
+
+    /// Consumes `--example`, `--input <path>` and `-` from `args`.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<InputSource, pico_args::Error> {
+        let example = args.contains("--example");
+        let path: Option<PathBuf> = args.opt_value_from_str("--input")?;
+        let stdin = args.contains("-");
+
+        let source = match (example, path, stdin) {
+            (false, None, false) => InputSource::Default,
+            (true, None, false) => InputSource::Example,
+            (false, Some(path), false) if path.as_os_str() == "-" => InputSource::Stdin,
+            (false, Some(path), false) => InputSource::Path(path),
+            (false, None, true) => InputSource::Stdin,
+            _ => {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "only one of --example, --input <path> and - can be given".to_owned(),
+                })
+            }
+        };
+        Ok(source)
+    }
+
+    pub fn read(&self, day: u8) -> anyhow::Result<String> {
+        match self {
+            InputSource::Default => crate::try_read_file("inputs", day)
+                .with_context(|| format!("could not open input file for day {:02}", day)),
+            InputSource::Example => Ok(self.read_with_params::<()>(day)?.0),
+            InputSource::Path(path) => fs::read_to_string(path)
+                .with_context(|| format!("could not open input file '{}'", path.display())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("could not read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Reads the input together with its parameters.
+    ///
+    /// The parameters are the defaults for the real input, except for example cases, which
+    /// can override them in their header.
+    pub fn read_with_params<P: Params>(&self, day: u8) -> anyhow::Result<(String, P)> {
+        match self {
+            InputSource::Example => match crate::try_read_file("examples", day) {
+                Ok(input) => Ok((input, P::default())),
+                Err(_) => {
+                    let example = read_example(day, "example")?;
+                    let params = example.parse_params()?;
+                    Ok((example.input, params))
+                }
+            },
+            _ => Ok((self.read(day)?, P::default())),
+        }
+    }
+}
+
+/// Reads the input of a day binary from the source selected on the command line.
+///
+/// Exits the process with an error message if the input cannot be read.
+pub fn read_input(day: u8) -> String {
+    read_input_with_params::<()>(day).0
+}
+
+/// Reads the input and parameters of a day binary as selected on the command line.
+///
+/// Parameters can be overridden with any number of `--param key=value` arguments.
+/// Exits the process with an error message if either cannot be read.
+pub fn read_input_with_params<P: Params>(day: u8) -> (String, P) {
+    match try_read_input_with_params(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn try_read_input_with_params<P: Params>(day: u8) -> anyhow::Result<(String, P)> {
+    let mut args = pico_args::Arguments::from_env();
+    let source = InputSource::from_args(&mut args)?;
+    let overrides: Vec<String> = args.values_from_str("--param")?;
+    reject_unknown_args(args)?;
+
+    let (input, mut params) = source.read_with_params::<P>(day)?;
+    for o in &overrides {
+        let (key, value) = parse_override(o)?;
+        params.set(&key, &value)?;
+    }
+    Ok((input, params))
+}
+
+/// Fails on arguments that no part of a day binary reads, e.g. a misspelled `--inptu`.
+///
+/// The bench, animation and frame settings are read where they are used, so they are only
+/// consumed here.
+fn reject_unknown_args(mut args: pico_args::Arguments) -> anyhow::Result<()> {
+    BenchConfig::from_args(&mut args)?;
+    Animation::from_args(&mut args)?;
+    FrameWriter::parse_args(&mut args)?;
+
+    let unknown = args.finish();
+    if !unknown.is_empty() {
+        let unknown: Vec<_> = unknown.iter().map(|a| a.to_string_lossy()).collect();
+        bail!("unknown arguments: {}", unknown.join(" "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<InputSource, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_vec(args.iter().map(Into::into).collect());
+        InputSource::from_args(&mut args)
+    }
+
+    #[test]
+    fn test_input_source_from_args() {
+        assert_eq!(parse(&[]).unwrap(), InputSource::Default);
+        assert_eq!(parse(&["--example"]).unwrap(), InputSource::Example);
+        assert_eq!(parse(&["-"]).unwrap(), InputSource::Stdin);
+        assert_eq!(parse(&["--input", "-"]).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            parse(&["--bench", "--input", "gen/17.txt"]).unwrap(),
+            InputSource::Path(PathBuf::from("gen/17.txt"))
+        );
+        assert!(parse(&["--example", "-"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_args() {
+        let args =
+            |args: &[&str]| pico_args::Arguments::from_vec(args.iter().map(Into::into).collect());
+        assert!(reject_unknown_args(args(&["--bench", "--frame-every", "3"])).is_ok());
+        let err = reject_unknown_args(args(&["--inptu", "foo.txt"])).unwrap_err();
+        assert_eq!(err.to_string(), "unknown arguments: --inptu foo.txt");
+    }
+}
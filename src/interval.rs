@@ -0,0 +1,346 @@
+use std::{
+    iter,
+    ops::{Range, RangeInclusive},
+};
+
+use num::PrimInt;
+
+/// Ranges of integers that can be turned into a half-open interval.
+pub trait IntoInterval<T> {
+    fn into_interval(self) -> Range<T>;
+}
+
+impl<T: PrimInt> IntoInterval<T> for Range<T> {
+    fn into_interval(self) -> Range<T> {
+        self
+    }
+}
+
+/// Panics if the end is the largest value of the type, which has no half-open equivalent.
+impl<T: PrimInt> IntoInterval<T> for RangeInclusive<T> {
+    fn into_interval(self) -> Range<T> {
+        let (start, end) = self.into_inner();
+        let end = end
+            .checked_add(&T::one())
+            .expect("inclusive range ending at the largest value");
+        start..end
+    }
+}
+
+/// Comparisons between two inclusive ranges, without building an [`IntervalSet`].
+pub trait RangeInclusiveExt<T> {
+    /// Whether all integers of `other` are in this range.
+    fn contains_range(&self, other: &Self) -> bool;
+    /// Whether any integer is in both ranges.
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+impl<T: PrimInt> RangeInclusiveExt<T> for RangeInclusive<T> {
+    fn contains_range(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start() <= other.start() && other.end() <= self.end())
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start() <= other.end()
+            && other.start() <= self.end()
+    }
+}
+
+/// A set of integers stored as sorted, disjoint half-open intervals.
+///
+/// Touching or overlapping intervals are merged on insertion, so the representation of a set
+/// is unique and two sets are equal if they contain the same integers.
+///
+/// As the intervals are half-open, the set cannot hold the largest value of `T`, and methods
+/// taking an inclusive range ending at it panic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Non-empty intervals with a gap between each of them.
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the integers of the range, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, range: impl IntoInterval<T>) {
+        let r = range.into_interval();
+        if r.start >= r.end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|x| x.end < r.start);
+        let hi = self.ranges.partition_point(|x| x.start <= r.end);
+        let mut merged = r;
+        if lo < hi {
+            merged.start = merged.start.min(self.ranges[lo].start);
+            merged.end = merged.end.max(self.ranges[hi - 1].end);
+        }
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    /// Removes the integers of the range, splitting the intervals it falls inside of.
+    pub fn remove(&mut self, range: impl IntoInterval<T>) {
+        let r = range.into_interval();
+        if r.start >= r.end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|x| x.end <= r.start);
+        let hi = self.ranges.partition_point(|x| x.start < r.end);
+        if lo == hi {
+            return;
+        }
+        let (first, last) = (self.ranges[lo].clone(), self.ranges[hi - 1].clone());
+        let left = (first.start < r.start).then_some(first.start..r.start);
+        let right = (last.end > r.end).then_some(r.end..last.end);
+        self.ranges.splice(lo..hi, left.into_iter().chain(right));
+    }
+
+    /// The integers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in other.iter() {
+            result.insert(r);
+        }
+        result
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in other.iter() {
+            result.remove(r);
+        }
+        result
+    }
+
+    /// The integers in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The integers of the range that are not in this set.
+    pub fn complement(&self, within: impl IntoInterval<T>) -> Self {
+        IntervalSet {
+            ranges: self.gaps_within(within).collect(),
+        }
+    }
+
+    /// The missing intervals of the range in increasing order, without building a new set.
+    pub fn gaps_within(&self, within: impl IntoInterval<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let Range { start, end } = within.into_interval();
+        let mut i = self.ranges.partition_point(|x| x.end <= start);
+        let mut cursor = start;
+        iter::from_fn(move || {
+            while cursor < end {
+                match self.ranges.get(i) {
+                    Some(x) if x.start < end => {
+                        i += 1;
+                        let gap = cursor..x.start;
+                        cursor = cursor.max(x.end);
+                        if gap.start < gap.end {
+                            return Some(gap);
+                        }
+                    }
+                    _ => {
+                        let gap = cursor..end;
+                        cursor = end;
+                        return Some(gap);
+                    }
+                }
+            }
+            None
+        })
+    }
+
+    /// The missing intervals between the first and the last integer of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].end..w[1].start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|x| x.end <= value);
+        self.ranges.get(i).is_some_and(|x| x.start <= value)
+    }
+
+    /// Whether all integers of the range are in the set.
+    pub fn contains_range(&self, range: impl IntoInterval<T>) -> bool {
+        let r = range.into_interval();
+        if r.start >= r.end {
+            return true;
+        }
+        let i = self.ranges.partition_point(|x| x.end <= r.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|x| x.start <= r.start && r.end <= x.end)
+    }
+
+    /// Whether any integer of the range is in the set.
+    pub fn overlaps(&self, range: impl IntoInterval<T>) -> bool {
+        let r = range.into_interval();
+        let i = self.ranges.partition_point(|x| x.end <= r.start);
+        r.start < r.end && self.ranges.get(i).is_some_and(|x| x.start < r.end)
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|r| (r.end.to_i128().unwrap() - r.start.to_i128().unwrap()) as u64)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The intervals of the set in increasing order, as half-open ranges.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// The intervals of the set in increasing order, as inclusive ranges.
+    pub fn iter_inclusive(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|r| r.start..=r.end - T::one())
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+/// Collects ranges by sorting them first, which is faster than inserting them one by one.
+impl<T: PrimInt, R: IntoInterval<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter
+            .into_iter()
+            .map(R::into_interval)
+            .filter(|r| r.start < r.end)
+            .collect();
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        // merge in place, keeping the first `len` ranges
+        let mut len = 0;
+        for i in 0..ranges.len() {
+            let r = ranges[i].clone();
+            if len > 0 && r.start <= ranges[len - 1].end {
+                ranges[len - 1].end = ranges[len - 1].end.max(r.end);
+            } else {
+                ranges[len] = r;
+                len += 1;
+            }
+        }
+        ranges.truncate(len);
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: PrimInt, R: IntoInterval<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for r in iter {
+            self.insert(r);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = IntervalSet::new();
+        set.insert(5..8);
+        set.insert(0..=1);
+        set.insert(10..12);
+        set.insert(2..3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..3, 5..8, 10..12]);
+        set.insert(6..11);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..3, 5..12]);
+        assert_eq!(set.len(), 10);
+
+        set.remove(1..=7);
+        assert_eq!(set.iter_inclusive().collect::<Vec<_>>(), [0..=0, 8..=11]);
+        set.remove(-5..100);
+        assert!(set.is_empty());
+
+        let collected: IntervalSet<i32> = [10..=12, 0..=1, 2..=4, 11..=20].into_iter().collect();
+        assert_eq!(collected.iter().collect::<Vec<_>>(), [0..5, 10..21]);
+        let mut extended = IntervalSet::new();
+        extended.extend([10..=12, 0..=1, 2..=4, 11..=20]);
+        assert_eq!(extended, collected);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<i64> = [5..25, 40..50].into_iter().collect();
+
+        let union = a.union(&b);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [0..30, 40..50]);
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), [5..10, 20..25]);
+        let difference = a.difference(&b);
+        assert_eq!(difference.iter().collect::<Vec<_>>(), [0..5, 25..30]);
+        assert!(union.gaps().eq(iter::once(30..40)));
+        assert_eq!(a.gaps_within(5..35).collect::<Vec<_>>(), [10..20, 30..35]);
+        assert_eq!(a.gaps_within(0..30).count(), 1);
+        assert_eq!(a.gaps_within(2..8).count(), 0);
+        assert_eq!(
+            a.complement(-5..=24).iter().collect::<Vec<_>>(),
+            [-5..0, 10..20]
+        );
+
+        assert!(a.contains(0) && a.contains(29));
+        assert!(!a.contains(10) && !a.contains(-1));
+        assert!(a.contains_range(2..=9) && !a.contains_range(2..=10));
+        assert!(a.overlaps(9..20) && !a.overlaps(10..20));
+    }
+
+    #[test]
+    fn test_inclusive_range_comparisons() {
+        assert!((2..=8).contains_range(&(3..=8)) && !(2..=8).contains_range(&(1..=3)));
+        assert!((0..=u8::MAX).contains_range(&(7..=u8::MAX)));
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 5..=4;
+        assert!((2..=3).contains_range(&empty) && !(2..=8).overlaps(&empty));
+        assert!((2..=4).overlaps(&(4..=6)) && !(2..=4).overlaps(&(5..=6)));
+    }
+}
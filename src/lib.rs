@@ -5,9 +5,27 @@
  */
 use std::env;
 use std::fs;
-
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod all_pairs;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod cube;
+pub mod diamond;
+pub mod examples;
+pub mod grid;
 pub mod helpers;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod ocr;
+pub mod params;
 pub mod point;
+pub mod readme;
+pub mod record;
+pub mod render;
 pub mod shortest_path;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -16,112 +34,79 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 #[macro_export]
 macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
-        use aoc::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
+    ($day:expr, $part:expr, $solver:expr, $input:expr, $params:expr) => {{
+        let params = $params;
+        aoc::solve!($day, $part, |input: &str| ($solver)(input, params), $input);
+    }};
+    ($day:expr, $part:expr, $solver:expr, $input:expr) => {{
+        let bench = aoc::bench::BenchConfig::from_env();
+        let mut record = aoc::record::Record::run($day, $part, $solver, $input, bench.as_ref());
+        record.verify();
+        record.print();
+
+        if let Err(e) = record.append_to_env_file() {
+            eprintln!("could not write record: {}", e);
         }
-
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
-
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
-}
-
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
+/// Generates one test per example case in `src/examples/NN/`.
+///
+/// Without solvers, `part_one` and `part_two` are called with the input. With `params`, they
+/// are also passed the day's `Params` as given in the example header. Other solvers can be
+/// given as closures taking the input and the [`examples::Example`].
+#[macro_export]
+macro_rules! example_tests {
+    ($day:literal, params) => {
+        $crate::example_tests!(
+            $day,
+            part_one: |input, example| part_one(input, &example.parse_params::<Params>().unwrap()),
+            part_two: |input, example| part_two(input, &example.parse_params::<Params>().unwrap())
+        );
+    };
+    ($day:literal) => {
+        $crate::example_tests!(
+            $day,
+            part_one: |input, _| part_one(input),
+            part_two: |input, _| part_two(input)
+        );
+    };
+    ($day:literal, part_one: $part_one:expr, part_two: $part_two:expr $(,)?) => {
+        #[cfg(test)]
+        mod example_cases {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn run_example(name: &str) {
+                $crate::examples::check_case($day, name, $part_one, $part_two);
             }
+
+            include!(concat!(env!("OUT_DIR"), "/examples_", stringify!($day), ".rs"));
         }
-    })
+    };
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+pub fn read_file(folder: &str, day: u8) -> String {
+    try_read_file(folder, day).expect("could not open input file")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
+pub fn try_read_file(folder: &str, day: u8) -> io::Result<String> {
+    let filepath = repo_file(
+        Path::new("src")
+            .join(folder)
+            .join(format!("{:02}.txt", day)),
+    )?;
+    fs::read_to_string(filepath)
+}
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+/// A file of the repository, relative to its root: in the working directory if it exists
+/// there, otherwise in the directory the crate was built from, so that binaries can be run
+/// from anywhere.
+fn repo_file(relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let local = env::current_dir()?.join(&relative);
+    if local.is_file() {
+        Ok(local)
+    } else {
+        Ok(Path::new(env!("CARGO_MANIFEST_DIR")).join(relative))
     }
 }
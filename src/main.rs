@@ -2,45 +2,289 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use aoc::baseline::{self, DEFAULT_THRESHOLD};
+use aoc::bench::BenchConfig;
+use aoc::readme::{update_readme, DayTimings};
+use aoc::record::Record;
 use aoc::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use std::fs;
+use std::panic::catch_unwind;
+use std::path::{Path, PathBuf};
+use std::process;
 
-fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+const README_PATH: &str = "README.md";
+
+/// A single day compiled into the runner.
+struct Day {
+    number: u8,
+    part_one: fn(&str, Option<&BenchConfig>) -> Record,
+    part_two: fn(&str, Option<&BenchConfig>) -> Record,
+}
+
+/// Declares every day's binary as a module of the runner and registers its solvers in `DAYS`.
+///
+/// Days marked `with params` are run with the default `Params` of the module.
+macro_rules! days {
+    (@solver $module:ident::$part:ident) => {
+        $module::$part
+    };
+    (@solver $module:ident::$part:ident with params) => {
+        |input: &str| $module::$part(input, &$module::Params::default())
+    };
+    ($($module:ident: $day:literal => $path:literal $(with $params:ident)?),* $(,)?) => {
+        $(
+            #[path = $path]
+            #[allow(dead_code)]
+            mod $module;
+        )*
+
+        const DAYS: &[Day] = &[$(
+            Day {
+                number: $day,
+                part_one: |input, bench| {
+                    Record::run($day, 1, days!(@solver $module::part_one $(with $params)?), input, bench)
+                },
+                part_two: |input, bench| {
+                    Record::run($day, 2, days!(@solver $module::part_two $(with $params)?), input, bench)
+                },
+            }
+        ),*];
+    };
+}
 
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
+days! {
+    day01: 1 => "bin/01.rs",
+    day02: 2 => "bin/02.rs",
+    day03: 3 => "bin/03.rs",
+    day04: 4 => "bin/04.rs",
+    day05: 5 => "bin/05.rs",
+    day06: 6 => "bin/06.rs",
+    day07: 7 => "bin/07.rs" with params,
+    day08: 8 => "bin/08.rs",
+    day09: 9 => "bin/09.rs",
+    day10: 10 => "bin/10.rs",
+    day11: 11 => "bin/11.rs",
+    day12: 12 => "bin/12.rs",
+    day13: 13 => "bin/13.rs",
+    day14: 14 => "bin/14.rs",
+    day15: 15 => "bin/15.rs" with params,
+    day16: 16 => "bin/16.rs" with params,
+    day17: 17 => "bin/17.rs",
+    day18: 18 => "bin/18.rs",
+    day19: 19 => "bin/19.rs",
+    day20: 20 => "bin/20.rs",
+    day21: 21 => "bin/21.rs",
+    day22: 22 => "bin/22.rs",
+    day23: 23 => "bin/23.rs",
+    day24: 24 => "bin/24.rs",
+    day25: 25 => "bin/25.rs",
+}
+
+struct Args {
+    days: Vec<u8>,
+    bench: Option<BenchConfig>,
+    readme: bool,
+    verify: bool,
+    save_baseline: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let bench = BenchConfig::from_args(&mut args)?;
+    let readme = args.contains("--readme");
+    let verify = args.contains("--verify");
+    let save_baseline = args.opt_value_from_str("--save-baseline")?;
+    let compare = args.opt_value_from_str("--compare")?;
+    let threshold = args
+        .opt_value_from_str("--threshold")?
+        .unwrap_or(DEFAULT_THRESHOLD);
+    let days = args
+        .finish()
+        .iter()
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            arg.parse()
+                .map_err(|_| pico_args::Error::Utf8ArgumentParsingFailed {
+                    value: arg.to_string(),
+                    cause: "not a day".to_owned(),
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Args {
+        days,
+        bench,
+        readme,
+        verify,
+        save_baseline,
+        compare,
+        threshold,
+    })
+}
+
+fn run_day(day: &Day, bench: Option<&BenchConfig>) -> Vec<Record> {
+    let input = match aoc::try_read_file("inputs", day.number) {
+        Ok(input) => input,
+        Err(_) => {
+            println!("Not solved.");
+            return Vec::new();
+        }
+    };
+
+    let mut records = Vec::new();
+    for (part, solver) in [(1, day.part_one), (2, day.part_two)] {
+        // a panicking solution should not take down the rest of the days with it.
+        let mut record = catch_unwind(|| solver(&input, bench))
+            .unwrap_or_else(|_| Record::failed(day.number, part, "panicked"));
+        record.verify();
+        record.print();
+        if let Err(e) = record.append_to_env_file() {
+            eprintln!("could not write record: {}", e);
+        }
+        records.push(record);
+    }
+    records
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
 
+    let records: Vec<Record> = DAYS
+        .iter()
+        .filter(|day| args.days.is_empty() || args.days.contains(&day.number))
+        .flat_map(|day| {
             println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
+            println!("{}| Day {:02} |{}", ANSI_BOLD, day.number, ANSI_RESET);
             println!("----------");
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
-                }
-            );
-
-            if is_empty {
-                0_f64
-            } else {
-                aoc::parse_exec_time(&output)
-            }
+            run_day(day, args.bench.as_ref())
         })
+        .collect();
+
+    let total_ns: u64 = records
+        .iter()
+        .filter(|r| r.answer.is_some())
+        .map(|r| r.elapsed_ns)
         .sum();
 
     println!(
         "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
+        ANSI_BOLD,
+        ANSI_RESET,
+        ANSI_ITALIC,
+        total_ns as f64 / 1_000_000_f64,
+        ANSI_RESET
     );
+
+    let wrong_answers = records
+        .iter()
+        .filter(|r| r.error.is_none() && r.is_correct() == Some(false))
+        .count();
+    let failures = records.iter().filter(|r| r.error.is_some()).count();
+    if args.verify && wrong_answers + failures > 0 {
+        eprintln!(
+            "{} part(s) gave a wrong answer, {} part(s) failed.",
+            wrong_answers, failures
+        );
+        process::exit(1);
+    }
+
+    if args.readme {
+        if let Err(e) = write_readme(&records) {
+            eprintln!("Failed to update {}: {:#}", README_PATH, e);
+            process::exit(1);
+        }
+        println!("🎄 Updated results in {}.", README_PATH);
+    }
+
+    if let Some(path) = &args.save_baseline {
+        if let Err(e) = baseline::save_baseline(path, &records) {
+            eprintln!("Failed to save baseline: {:#}", e);
+            process::exit(1);
+        }
+        println!("🎄 Saved baseline to {}.", path.display());
+    }
+
+    if let Some(path) = &args.compare {
+        match compare_to_baseline(path, &records, args.threshold) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Failed to compare to baseline: {:#}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Prints the change of each part compared to the baseline and returns false on regressions.
+fn compare_to_baseline(path: &Path, records: &[Record], threshold: f64) -> anyhow::Result<bool> {
+    let comparisons = baseline::compare(&baseline::load_baseline(path)?, records);
+
+    println!("----------");
+    println!("{}| Baseline |{}", ANSI_BOLD, ANSI_RESET);
+    println!("----------");
+
+    let mut regressions = 0;
+    for c in &comparisons {
+        let regression = c.is_regression(threshold);
+        if regression {
+            regressions += 1;
+        }
+
+        println!(
+            "Day {:02} / Part {}: {:>10.2?} -> {:>10.2?} {}{:+7.1}%{}{}",
+            c.day,
+            c.part,
+            c.baseline(),
+            c.current(),
+            ANSI_ITALIC,
+            c.change_percent(),
+            ANSI_RESET,
+            if regression { " ❌" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        println!(
+            "{}{} part(s) regressed by more than {}%.{}",
+            ANSI_BOLD, regressions, threshold, ANSI_RESET
+        );
+    }
+    Ok(regressions == 0)
+}
+
+fn write_readme(records: &[Record]) -> anyhow::Result<()> {
+    let mut timings: Vec<DayTimings> = Vec::new();
+    for record in records.iter().filter(|r| r.answer.is_some()) {
+        let index = match timings.iter().position(|t| t.day == record.day) {
+            Some(index) => index,
+            None => {
+                timings.push(DayTimings {
+                    day: record.day,
+                    part_one: None,
+                    part_two: None,
+                });
+                timings.len() - 1
+            }
+        };
+
+        let t = &mut timings[index];
+        match record.part {
+            1 => t.part_one = Some(record.elapsed()),
+            _ => t.part_two = Some(record.elapsed()),
+        }
+    }
+
+    let readme = fs::read_to_string(README_PATH)?;
+    fs::write(README_PATH, update_readme(&readme, &timings)?)?;
+    Ok(())
 }
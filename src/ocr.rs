@@ -0,0 +1,207 @@
+use anyhow::bail;
+
+use crate::grid::Grid;
+
+/// The font of letters 6 rows high and up to 5 wide drawn on a CRT or with dots, e.g. 2022
+/// day 10.
+///
+/// Glyphs are trimmed to their lit columns, so most letters are 4 wide, `I` is 3 and `Y` is 5.
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 6×10 font of letters formed by moving points, e.g. 2018 day 10.
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters drawn in a bitmap of `#` and `.` (or space) lines, e.g. `"RGLRBZAU"`.
+///
+/// The font is chosen by the height of the lit area: 6 rows for the font up to 5 wide and 10
+/// rows for the 6×10 one. Letters are separated by at least one unlit column.
+pub fn recognize(bitmap: &str) -> anyhow::Result<String> {
+    let rows = bitmap
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    recognize_rows(rows)
+}
+
+/// Like [`recognize`], for a grid of lit cells.
+pub fn recognize_grid(grid: &Grid<bool>) -> anyhow::Result<String> {
+    recognize_rows(grid.rows().map(|row| row.to_vec()).collect())
+}
+
+fn recognize_rows(mut rows: Vec<Vec<bool>>) -> anyhow::Result<String> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, false);
+    }
+    let first = rows.iter().position(|row| row.contains(&true));
+    let last = rows.iter().rposition(|row| row.contains(&true));
+    let rows = match (first, last) {
+        (Some(first), Some(last)) => &rows[first..=last],
+        _ => bail!("no letters in the bitmap"),
+    };
+
+    let font: &[(char, &str)] = match rows.len() {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        height => bail!("letters are {} rows high, expected 6 or 10", height),
+    };
+
+    let lit = |x: usize| rows.iter().any(|row| row[x]);
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if !lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && lit(x) {
+            x += 1;
+        }
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                row[start..x]
+                    .iter()
+                    .map(|&b| if b { '#' } else { '.' })
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        match font.iter().find(|(_, g)| *g == glyph) {
+            Some(&(c, _)) => letters.push(c),
+            None => bail!("unknown glyph at column {}:\n{}", start, glyph),
+        }
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognize_small_font() {
+        let bitmap = "\
+###..####.#.....###..
+#..#.#....#....#.....
+#..#.###..#....#.....
+###..#....#.....##...
+#.#..#....#.......#..
+#..#.####.####.###...
+";
+        assert_eq!(recognize(bitmap).unwrap(), "RELS");
+
+        let grid = bitmap.parse::<Grid<char>>().unwrap().map(|&c| c == '#');
+        assert_eq!(recognize_grid(&grid).unwrap(), "RELS");
+    }
+
+    #[test]
+    fn test_recognize_large_font() {
+        // a message in the style of 2018 day 10, with a border of unlit points around it
+        let message = "\
+..................................................................
+..#####...#.......######..######..#....#..#####.....##....#....#..
+..#....#..#.......#............#..##...#..#....#...#..#...##...#..
+..#....#..#.......#............#..##...#..#....#..#....#..##...#..
+..#....#..#.......#...........#...#.#..#..#....#..#....#..#.#..#..
+..#####...#.......#####......#....#.#..#..#####...#....#..#.#..#..
+..#..#....#.......#.........#.....#..#.#..#..#....######..#..#.#..
+..#...#...#.......#........#......#..#.#..#...#...#....#..#..#.#..
+..#...#...#.......#.......#.......#...##..#...#...#....#..#...##..
+..#....#..#.......#.......#.......#...##..#....#..#....#..#...##..
+..#....#..######..######..######..#....#..#....#..#....#..#....#..
+..................................................................";
+        assert_eq!(recognize(message).unwrap(), "RLEZNRAN");
+        assert_eq!(recognize("\n###\n.#.\n.#.\n.#.\n.#.\n###\n").unwrap(), "I");
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let error = recognize("#.#\n.#.\n#.#\n.#.\n#.#\n.#.").unwrap_err();
+        assert!(error.to_string().starts_with("unknown glyph at column 0"));
+        assert!(recognize("#\n#").is_err());
+    }
+}
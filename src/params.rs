@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+
+/// Puzzle constants of a day that differ between the example and the real input.
+///
+/// The default values are the ones for the real input. They can be overridden from the header
+/// of an example case or with `--param key=value` on the command line. Use [`crate::params!`]
+/// to declare the struct instead of implementing this by hand.
+pub trait Params: Default {
+    fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()>;
+
+    /// Returns the default parameters with the given `key`/`value` pairs applied.
+    fn with_overrides<'a>(
+        overrides: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> anyhow::Result<Self> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters.
+impl Params for () {
+    fn set(&mut self, key: &str, _: &str) -> anyhow::Result<()> {
+        Err(unknown_param(key))
+    }
+}
+
+pub fn parse_value<T>(key: &str, value: &str) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    value
+        .parse()
+        .map_err(Into::into)
+        .with_context(|| format!("invalid value '{}' for parameter '{}'", value, key))
+}
+
+pub fn unknown_param(key: &str) -> anyhow::Error {
+    anyhow!("unknown parameter '{}'", key)
+}
+
+/// Splits a `key=value` command line override.
+pub fn parse_override(s: &str) -> anyhow::Result<(String, String)> {
+    match s.split_once('=') {
+        Some((key, value)) => Ok((key.trim().to_owned(), value.trim().to_owned())),
+        None => bail!("expected 'key=value', got '{}'", s),
+    }
+}
+
+/// Declares the parameters of a day with their defaults for the real input.
+///
+/// ```
+/// aoc::params! {
+///     pub struct Params {
+///         y: i32 = 2_000_000,
+///         max: i32 = 4_000_000,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> ::anyhow::Result<()> {
+                match key {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(key, value)?,)*
+                    _ => return Err($crate::params::unknown_param(key)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        struct TestParams {
+            y: i32 = 2_000_000,
+            minutes: u16 = 30,
+        }
+    }
+
+    #[test]
+    fn test_params_overrides() {
+        assert_eq!(
+            TestParams::with_overrides([]).unwrap(),
+            TestParams {
+                y: 2_000_000,
+                minutes: 30
+            }
+        );
+        assert_eq!(TestParams::with_overrides([("y", "10")]).unwrap().y, 10);
+        assert!(TestParams::with_overrides([("y", "ten")]).is_err());
+        assert!(TestParams::with_overrides([("x", "10")]).is_err());
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("max=20").unwrap(),
+            ("max".to_owned(), "20".to_owned())
+        );
+        assert!(parse_override("max").is_err());
+    }
+}
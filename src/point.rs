@@ -1,11 +1,14 @@
 use std::{
     fmt::{Debug, Display},
-    ops::Add,
+    ops::{Add, Mul, Neg, Sub},
     str::FromStr,
 };
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, ensure, Context};
+use bitvec::vec::BitVec;
 use enum_iterator::{all, Sequence};
+use itertools::iproduct;
+use num::{PrimInt, Signed};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
 pub struct Point<T> {
@@ -13,6 +16,12 @@ pub struct Point<T> {
     pub y: T,
 }
 
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
 impl<T> Point<T>
 where
     T: Default,
@@ -22,21 +31,112 @@ where
     }
 }
 
-impl Point<i8> {
-    pub fn manhattan_distance(&self, p: &Self) -> u8 {
-        self.x.abs_diff(p.x) + self.y.abs_diff(p.y)
+/// The difference between two coordinates, computed in 128 bits so that it cannot overflow.
+fn abs_diff<T: PrimInt>(a: T, b: T) -> u128 {
+    let wide = |v: T| v.to_i128().expect("coordinate does not fit i128");
+    wide(a).abs_diff(wide(b))
+}
+
+fn to_distance(d: Option<u128>) -> u64 {
+    d.and_then(|d| u64::try_from(d).ok())
+        .expect("distance does not fit u64")
+}
+
+/// Distances are computed in 128 bits and returned as `u64`.
+///
+/// Panics if the distance does not fit `u64`, which can happen for points far apart with 64-bit
+/// coordinates, or with 32-bit ones for the squared euclidean distance.
+impl<T: PrimInt> Point<T> {
+    fn abs_deltas(&self, p: &Self) -> (u128, u128) {
+        (abs_diff(self.x, p.x), abs_diff(self.y, p.y))
+    }
+
+    pub fn manhattan_distance(&self, p: &Self) -> u64 {
+        let (dx, dy) = self.abs_deltas(p);
+        to_distance(dx.checked_add(dy))
+    }
+
+    /// The number of king moves between the points.
+    pub fn chebyshev_distance(&self, p: &Self) -> u64 {
+        let (dx, dy) = self.abs_deltas(p);
+        to_distance(Some(dx.max(dy)))
+    }
+
+    /// The squared euclidean distance, which compares like the distance but stays an integer.
+    pub fn euclidean_distance_squared(&self, p: &Self) -> u64 {
+        let (dx, dy) = self.abs_deltas(p);
+        let square = |d: u128| d.checked_mul(d);
+        to_distance(
+            square(dx)
+                .zip(square(dy))
+                .and_then(|(x, y)| x.checked_add(y)),
+        )
     }
 }
 
-impl Point<i16> {
-    pub fn manhattan_distance(&self, p: &Self) -> u16 {
-        self.x.abs_diff(p.x) + self.y.abs_diff(p.y)
+/// Rotations by 90°, with `y` growing downwards as on screen, so that turning right from
+/// north leads east.
+impl<T: Copy + Neg<Output = T>> Point<T> {
+    /// Rotates clockwise around the origin.
+    pub fn rotate_right(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotates counterclockwise around the origin.
+    pub fn rotate_left(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+
+    pub fn rotate_right_around(self, pivot: Self) -> Self
+    where
+        T: Add<Output = T> + Sub<Output = T>,
+    {
+        (self - pivot).rotate_right() + pivot
+    }
+
+    pub fn rotate_left_around(self, pivot: Self) -> Self
+    where
+        T: Add<Output = T> + Sub<Output = T>,
+    {
+        (self - pivot).rotate_left() + pivot
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
     }
 }
 
-impl Point<i32> {
-    pub fn manhattan_distance(&self, p: &Self) -> u32 {
-        self.x.abs_diff(p.x) + self.y.abs_diff(p.y)
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
     }
 }
 
@@ -79,37 +179,48 @@ impl<T> Point<T> {
     }
 }
 
-impl Add<CardinalDirection> for Point<i8> {
+impl<T: PrimInt> Add<CardinalDirection> for Point<T> {
     type Output = Self;
 
     fn add(self, rhs: CardinalDirection) -> Self::Output {
         let (dx, dy) = rhs.deltas();
-        Point {
-            x: self.x + dx as i8,
-            y: self.y + dy as i8,
-        }
+        self.offset(dx, dy)
     }
 }
 
-impl Add<CompassDirection> for Point<i8> {
+impl<T: PrimInt> Add<HexDirection> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: HexDirection) -> Self::Output {
+        let (dx, dy) = rhs.deltas();
+        self.offset(dx, dy)
+    }
+}
+
+impl<T: PrimInt> Add<CompassDirection> for Point<T> {
     type Output = Self;
 
     fn add(self, rhs: CompassDirection) -> Self::Output {
         let (dx, dy) = rhs.deltas();
-        Point {
-            x: self.x + dx as i8,
-            y: self.y + dy as i8,
-        }
+        self.offset(dx, dy)
     }
 }
 
-impl Point<i8> {
+impl<T: PrimInt> Point<T> {
+    /// Moves by direction deltas.
+    ///
+    /// Panics if the result does not fit the coordinate type, e.g. below zero for unsigned ones.
+    #[inline]
+    fn offset(self, dx: i32, dy: i32) -> Self {
+        let c = |v: T, d: i32| {
+            T::from(v.to_i64().unwrap() + d as i64)
+                .expect("point moved out of the coordinate range")
+        };
+        Point::new(c(self.x, dx), c(self.y, dy))
+    }
+
     pub fn towards_compass_direction(&self, d: CompassDirection) -> Self {
-        let (dx, dy) = d.deltas();
-        Point {
-            x: self.x + dx as i8,
-            y: self.y + dy as i8,
-        }
+        *self + d
     }
 }
 
@@ -142,9 +253,76 @@ impl CompassDirection {
             CompassDirection::SW => (-1, 1),
         }
     }
+
+    /// The direction a quarter turn clockwise from this one.
+    pub fn turn_right(self) -> Self {
+        match self {
+            CompassDirection::N => CompassDirection::E,
+            CompassDirection::E => CompassDirection::S,
+            CompassDirection::S => CompassDirection::W,
+            CompassDirection::W => CompassDirection::N,
+            CompassDirection::NE => CompassDirection::SE,
+            CompassDirection::SE => CompassDirection::SW,
+            CompassDirection::SW => CompassDirection::NW,
+            CompassDirection::NW => CompassDirection::NE,
+        }
+    }
+
+    /// The direction a quarter turn counterclockwise from this one.
+    pub fn turn_left(self) -> Self {
+        self.opposite().turn_right()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+impl From<CardinalDirection> for CompassDirection {
+    fn from(d: CardinalDirection) -> Self {
+        match d {
+            CardinalDirection::N => CompassDirection::N,
+            CardinalDirection::S => CompassDirection::S,
+            CardinalDirection::W => CompassDirection::W,
+            CardinalDirection::E => CompassDirection::E,
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Sequence)]
+/// A quarter turn, written `L` or `R` in puzzle inputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    pub fn from_letter(c: char) -> anyhow::Result<Self> {
+        match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            _ => bail!("invalid turn '{}'", c),
+        }
+    }
+}
+
+impl FromStr for Turn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        single_letter(s).and_then(Turn::from_letter)
+    }
+}
+
+fn single_letter(s: &str) -> anyhow::Result<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => bail!("expected a single letter, got '{}'", s),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
 pub enum CardinalDirection {
     N,
     S,
@@ -153,6 +331,10 @@ pub enum CardinalDirection {
 }
 
 impl CardinalDirection {
+    pub fn values() -> impl Iterator<Item = CardinalDirection> {
+        all::<CardinalDirection>()
+    }
+
     pub fn for_code(c: char) -> Self {
         match c {
             '^' => Self::N,
@@ -163,6 +345,17 @@ impl CardinalDirection {
         }
     }
 
+    /// Parses `U`, `D`, `L` and `R` as well as `N`, `S`, `W` and `E`, with up being north.
+    pub fn from_letter(c: char) -> anyhow::Result<Self> {
+        match c {
+            'U' | 'N' => Ok(Self::N),
+            'D' | 'S' => Ok(Self::S),
+            'L' | 'W' => Ok(Self::W),
+            'R' | 'E' => Ok(Self::E),
+            _ => bail!("invalid direction '{}'", c),
+        }
+    }
+
     pub fn deltas(self) -> (i32, i32) {
         match self {
             CardinalDirection::N => (0, -1),
@@ -171,4 +364,617 @@ impl CardinalDirection {
             CardinalDirection::E => (1, 0),
         }
     }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    /// The direction a quarter turn clockwise from this one.
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// The direction a quarter turn counterclockwise from this one.
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Index of the direction clockwise from east, which is how puzzles score facings:
+    /// `E` is 0, `S` is 1, `W` is 2 and `N` is 3.
+    pub fn index(self) -> usize {
+        match self {
+            CardinalDirection::E => 0,
+            CardinalDirection::S => 1,
+            CardinalDirection::W => 2,
+            CardinalDirection::N => 3,
+        }
+    }
+
+    /// The direction with the given [`CardinalDirection::index`], modulo 4.
+    pub fn from_index(i: usize) -> Self {
+        match i % 4 {
+            0 => CardinalDirection::E,
+            1 => CardinalDirection::S,
+            2 => CardinalDirection::W,
+            _ => CardinalDirection::N,
+        }
+    }
+}
+
+impl FromStr for CardinalDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        single_letter(s).and_then(CardinalDirection::from_letter)
+    }
+}
+
+/// The neighbours of a hexagon in a grid of pointy-topped hexagons, in clockwise order.
+///
+/// Deltas are axial coordinates: `x` grows to the east and `y` to the south-east.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
+pub enum HexDirection {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+impl HexDirection {
+    pub fn values() -> impl Iterator<Item = HexDirection> {
+        all::<HexDirection>()
+    }
+
+    pub fn deltas(self) -> (i32, i32) {
+        match self {
+            HexDirection::E => (1, 0),
+            HexDirection::SE => (0, 1),
+            HexDirection::SW => (-1, 1),
+            HexDirection::W => (-1, 0),
+            HexDirection::NW => (0, -1),
+            HexDirection::NE => (1, -1),
+        }
+    }
+
+    /// The direction a sixth of a turn clockwise from this one.
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    /// The direction a sixth of a turn counterclockwise from this one.
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as usize + 5)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_index(self as usize + 3)
+    }
+
+    fn from_index(i: usize) -> Self {
+        match i % 6 {
+            0 => HexDirection::E,
+            1 => HexDirection::SE,
+            2 => HexDirection::SW,
+            3 => HexDirection::W,
+            4 => HexDirection::NW,
+            _ => HexDirection::NE,
+        }
+    }
+
+    /// Splits a run of directions without delimiters, e.g. `esenee`.
+    pub fn parse_list(s: &str) -> anyhow::Result<Vec<HexDirection>> {
+        let mut result = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let len = if rest.starts_with(['n', 's']) { 2 } else { 1 };
+            // a cut inside a multibyte character leaves the rest to fail parsing as a whole
+            let (d, tail) = rest.split_at_checked(len).unwrap_or((rest, ""));
+            result.push(d.parse()?);
+            rest = tail;
+        }
+        Ok(result)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "e" => HexDirection::E,
+            "se" => HexDirection::SE,
+            "sw" => HexDirection::SW,
+            "w" => HexDirection::W,
+            "nw" => HexDirection::NW,
+            "ne" => HexDirection::NE,
+            _ => bail!("invalid hex direction '{}'", s),
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Default> Point3<T> {
+    pub fn origin() -> Self {
+        Point3::default()
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Point3<T> {
+    pub fn towards(&self, dx: T, dy: T, dz: T) -> Self {
+        Point3::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+}
+
+impl<T: PrimInt> Point3<T> {
+    /// Panics if the distance does not fit `u64`, like the distances of [`Point`].
+    pub fn manhattan_distance(&self, p: &Self) -> u64 {
+        let d = abs_diff(self.x, p.x).checked_add(abs_diff(self.y, p.y));
+        to_distance(d.and_then(|d| d.checked_add(abs_diff(self.z, p.z))))
+    }
+}
+
+impl<T: PrimInt + Signed> Point3<T> {
+    /// The 6 points sharing a face with this one.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        let (o, z) = (T::one(), T::zero());
+        [
+            (-o, z, z),
+            (o, z, z),
+            (z, -o, z),
+            (z, o, z),
+            (z, z, -o),
+            (z, z, o),
+        ]
+        .into_iter()
+        .map(move |(dx, dy, dz)| self.towards(dx, dy, dz))
+    }
+
+    /// The 26 points sharing a face, an edge or a corner with this one.
+    pub fn neighbors26(self) -> impl Iterator<Item = Self> {
+        let d = [-T::one(), T::zero(), T::one()];
+        iproduct!(d, d, d)
+            .filter(|&(dx, dy, dz)| !(dx.is_zero() && dy.is_zero() && dz.is_zero()))
+            .map(move |(dx, dy, dz)| self.towards(dx, dy, dz))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Display> Debug for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr<Err = std::num::ParseIntError>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coords = s.split(',');
+        let (Some(x), Some(y), Some(z), None) =
+            (coords.next(), coords.next(), coords.next(), coords.next())
+        else {
+            bail!("expected 'x,y,z', got '{}'", s);
+        };
+        let parse = |c: &str| {
+            c.trim()
+                .parse()
+                .with_context(|| format!("invalid point '{}'", s))
+        };
+        Ok(Point3::new(parse(x)?, parse(y)?, parse(z)?))
+    }
+}
+
+/// The 6 directions along the axes in 3D.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
+pub enum Direction3 {
+    NegX,
+    PosX,
+    NegY,
+    PosY,
+    NegZ,
+    PosZ,
+}
+
+impl Direction3 {
+    pub fn values() -> impl Iterator<Item = Direction3> {
+        all::<Direction3>()
+    }
+
+    pub fn deltas(self) -> (i32, i32, i32) {
+        match self {
+            Direction3::NegX => (-1, 0, 0),
+            Direction3::PosX => (1, 0, 0),
+            Direction3::NegY => (0, -1, 0),
+            Direction3::PosY => (0, 1, 0),
+            Direction3::NegZ => (0, 0, -1),
+            Direction3::PosZ => (0, 0, 1),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction3::NegX => Direction3::PosX,
+            Direction3::PosX => Direction3::NegX,
+            Direction3::NegY => Direction3::PosY,
+            Direction3::PosY => Direction3::NegY,
+            Direction3::NegZ => Direction3::PosZ,
+            Direction3::PosZ => Direction3::NegZ,
+        }
+    }
+}
+
+impl<T: PrimInt> Add<Direction3> for Point3<T> {
+    type Output = Self;
+
+    /// Panics if the result does not fit the coordinate type.
+    fn add(self, rhs: Direction3) -> Self::Output {
+        let (dx, dy, dz) = rhs.deltas();
+        let c = |v: T, d: i32| {
+            T::from(v.to_i64().unwrap() + d as i64)
+                .expect("point moved out of the coordinate range")
+        };
+        Point3::new(c(self.x, dx), c(self.y, dy), c(self.z, dz))
+    }
+}
+
+/// A set of points inside a fixed box, stored as one bit per voxel.
+#[derive(Clone, PartialEq, Eq)]
+pub struct VoxelSet<T> {
+    min: Point3<T>,
+    max: Point3<T>,
+    /// Size of the box along each axis.
+    size: [usize; 3],
+    bits: BitVec,
+}
+
+impl<T: PrimInt + Signed + Display> VoxelSet<T> {
+    /// Creates an empty set for the box between the two corners, inclusively.
+    pub fn new(min: Point3<T>, max: Point3<T>) -> Self {
+        let len = |lo: T, hi: T| {
+            let d = hi.to_i64().unwrap() - lo.to_i64().unwrap();
+            usize::try_from(d).map_or(0, |d| d + 1)
+        };
+        let size = [len(min.x, max.x), len(min.y, max.y), len(min.z, max.z)];
+        VoxelSet {
+            min,
+            max,
+            size,
+            bits: BitVec::repeat(false, size.iter().product()),
+        }
+    }
+
+    /// Creates a set of the points, with the box fitted around them and grown by `padding`.
+    ///
+    /// Fails if the padding is negative or the grown box does not fit the coordinate type.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point3<T>>,
+        padding: T,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            padding >= T::zero(),
+            "negative voxel box padding {}",
+            padding
+        );
+        let points: Vec<_> = points.into_iter().collect();
+        let (mut min, mut max) = match points.first() {
+            Some(&p) => (p, p),
+            None => {
+                let (zero, one) = (T::zero(), T::one());
+                return Ok(VoxelSet::new(
+                    Point3::new(zero, zero, zero),
+                    Point3::new(-one, -one, -one),
+                ));
+            }
+        };
+        for p in &points {
+            min = Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+
+        let grown = |p: Point3<T>, f: fn(&T, &T) -> Option<T>| {
+            Some(Point3::new(
+                f(&p.x, &padding)?,
+                f(&p.y, &padding)?,
+                f(&p.z, &padding)?,
+            ))
+        };
+        let (min, max) = grown(min, T::checked_sub)
+            .zip(grown(max, T::checked_add))
+            .ok_or_else(|| anyhow!("padding {} takes the voxel box out of range", padding))?;
+        let mut set = VoxelSet::new(min, max);
+        for p in points {
+            set.insert(p);
+        }
+        Ok(set)
+    }
+
+    /// Inclusive corners of the box.
+    pub fn bounds(&self) -> (Point3<T>, Point3<T>) {
+        (self.min, self.max)
+    }
+
+    pub fn in_bounds(&self, p: Point3<T>) -> bool {
+        self.index(p).is_some()
+    }
+
+    #[inline]
+    fn index(&self, p: Point3<T>) -> Option<usize> {
+        // offsets below the box wrap around to large values, so one comparison per axis suffices
+        let offset = |v: T, lo: T, len: usize| {
+            let d = v.to_i64()?.wrapping_sub(lo.to_i64()?) as u64;
+            (d < len as u64).then_some(d as usize)
+        };
+        let [w, h, d] = self.size;
+        let x = offset(p.x, self.min.x, w)?;
+        let y = offset(p.y, self.min.y, h)?;
+        let z = offset(p.z, self.min.z, d)?;
+        Some((z * h + y) * w + x)
+    }
+
+    fn point(&self, index: usize) -> Point3<T> {
+        let [w, h, _] = self.size;
+        // the offset may not fit `T` on its own when the box is wider than `T::MAX`
+        let c = |offset: usize, lo: T| T::from(lo.to_i64().unwrap() + offset as i64).unwrap();
+        Point3::new(
+            c(index % w, self.min.x),
+            c(index / w % h, self.min.y),
+            c(index / (w * h), self.min.z),
+        )
+    }
+
+    /// Whether the point is in the set; points outside of the box never are.
+    #[inline]
+    pub fn contains(&self, p: Point3<T>) -> bool {
+        self.index(p).is_some_and(|i| self.bits[i])
+    }
+
+    /// Adds the point and returns whether it was new.
+    ///
+    /// Panics if the point is outside of the box.
+    pub fn insert(&mut self, p: Point3<T>) -> bool {
+        let i = self
+            .index(p)
+            .unwrap_or_else(|| panic!("voxel {:?} outside of {:?}..={:?}", p, self.min, self.max));
+        !self.bits.replace(i, true)
+    }
+
+    /// Removes the point and returns whether it was present.
+    pub fn remove(&mut self, p: Point3<T>) -> bool {
+        match self.index(p) {
+            Some(i) => self.bits.replace(i, false),
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.not_any()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Point3<T>> + '_ {
+        self.bits.iter_ones().map(|i| self.point(i))
+    }
+
+    /// The points of the box reachable from `start` through face neighbours that are not in
+    /// this set, e.g. the air around a droplet when starting from a corner.
+    pub fn flood_fill(&self, start: Point3<T>) -> VoxelSet<T> {
+        let mut filled = VoxelSet::new(self.min, self.max);
+        if !self.in_bounds(start) || self.contains(start) {
+            return filled;
+        }
+
+        filled.insert(start);
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            for n in p.neighbors6() {
+                if self.in_bounds(n) && !self.contains(n) && filled.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        filled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = Point::new(3i16, -1);
+        assert_eq!(p + Point::new(1, 1), Point::new(4, 0));
+        assert_eq!(p - p, Point::origin());
+        assert_eq!(p * 2, Point::new(6, -2));
+        assert_eq!(-p, Point::new(-3, 1));
+        assert_eq!(p + CardinalDirection::N, Point::new(3, -2));
+        assert_eq!(Point::new(0u32, 5) + CompassDirection::SE, Point::new(1, 6));
+        assert_eq!(p.to_string().parse::<Point<i16>>().unwrap(), p);
+    }
+
+    #[test]
+    fn test_point_rotation_and_distances() {
+        let north = Point::new(0i8, -1);
+        assert_eq!(north.rotate_right(), Point::new(1, 0));
+        assert_eq!(north.rotate_left(), Point::new(-1, 0));
+        assert_eq!(
+            north
+                .rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            north
+        );
+        assert_eq!(
+            Point::new(3, 1).rotate_right_around(Point::new(2, 1)),
+            Point::new(2, 2)
+        );
+        assert_eq!(
+            Point::new(3, 1).rotate_left_around(Point::new(2, 1)),
+            Point::new(2, 0)
+        );
+
+        let (a, b) = (Point::new(-100i8, 100), Point::new(100, 97));
+        assert_eq!(a.manhattan_distance(&b), 203);
+        assert_eq!(a.chebyshev_distance(&b), 200);
+        assert_eq!(a.euclidean_distance_squared(&b), 40_009);
+        let (a, b) = (Point::new(u64::MAX, 0), Point::new(0, i64::MAX as u64));
+        assert_eq!(a.chebyshev_distance(&b), u64::MAX);
+        let (a, b) = (Point::new(i64::MIN, 0), Point::new(i64::MAX, 0));
+        assert_eq!(a.manhattan_distance(&b), u64::MAX);
+        assert!(std::panic::catch_unwind(|| a.euclidean_distance_squared(&b)).is_err());
+    }
+
+    #[test]
+    fn test_directions() {
+        use CardinalDirection::*;
+        assert_eq!(N.turn_right(), E);
+        assert_eq!(N.turn_left(), W);
+        assert_eq!(W.turn(Turn::Right).turn(Turn::Right), E);
+        assert!(CardinalDirection::values().all(|d| d.opposite().opposite() == d));
+        assert_eq!([E, S, W, N].map(CardinalDirection::index), [0, 1, 2, 3]);
+        assert_eq!("U".parse::<CardinalDirection>().unwrap(), N);
+        assert_eq!(CardinalDirection::from_letter('E').unwrap(), E);
+        assert!("UD".parse::<CardinalDirection>().is_err());
+        assert_eq!("L".parse::<Turn>().unwrap(), Turn::Left);
+        assert_eq!(CompassDirection::NE.turn_left(), CompassDirection::NW);
+        assert_eq!(CompassDirection::from(S).opposite(), CompassDirection::N);
+    }
+
+    #[test]
+    fn test_hex_and_3d_directions() {
+        let path = HexDirection::parse_list("nwwswee").unwrap();
+        assert_eq!(path.len(), 5);
+        let end = path.into_iter().fold(Point::new(0i32, 0), |p, d| p + d);
+        assert_eq!(end, Point::origin());
+        assert!(HexDirection::parse_list("nx").is_err());
+        assert!(HexDirection::parse_list("n").is_err());
+        assert!(HexDirection::parse_list("é").is_err());
+        assert!(HexDirection::parse_list("nä").is_err());
+        assert_eq!(HexDirection::NE.turn_right(), HexDirection::E);
+        assert!(HexDirection::values().all(|d| {
+            let (dx, dy) = d.deltas();
+            d.opposite().deltas() == (-dx, -dy) && d.turn_left().turn_right() == d
+        }));
+
+        let p = Point3::new(0i8, 0, 0);
+        let mut neighbors: Vec<_> = Direction3::values().map(|d| p + d).collect();
+        let mut expected: Vec<_> = p.neighbors6().collect();
+        neighbors.sort();
+        expected.sort();
+        assert_eq!(neighbors, expected);
+        assert_eq!(p + Direction3::PosZ + Direction3::PosZ.opposite(), p);
+    }
+
+    #[test]
+    fn test_point3() {
+        let p: Point3<i32> = "1,-2,3".parse().unwrap();
+        assert_eq!(p, Point3::new(1, -2, 3));
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert!("1,2,x".parse::<Point3<i32>>().is_err());
+
+        assert_eq!(p.manhattan_distance(&Point3::origin()), 6);
+        assert_eq!(p + p, p * 2);
+        assert_eq!(p - p, Point3::origin());
+        assert_eq!(-p, Point3::new(-1, 2, -3));
+        assert_eq!(p.neighbors6().count(), 6);
+        assert_eq!(p.neighbors26().count(), 26);
+        assert!(p.neighbors26().all(|n| n != p));
+    }
+
+    #[test]
+    fn test_voxel_set_flood_fill() {
+        // a hollow 3x3x3 cube
+        let shell = iproduct!(0..3, 0..3, 0..3)
+            .map(|(x, y, z)| Point3::new(x, y, z))
+            .filter(|&p| p != Point3::new(1, 1, 1));
+        let set = VoxelSet::<i8>::from_points(shell, 1).unwrap();
+        assert_eq!(set.len(), 26);
+        assert_eq!(
+            set.bounds(),
+            (Point3::new(-1, -1, -1), Point3::new(3, 3, 3))
+        );
+        assert!(set.iter().all(|p| set.contains(p)));
+
+        let outside = set.flood_fill(Point3::new(-1, -1, -1));
+        assert_eq!(outside.len(), 5 * 5 * 5 - 27);
+        assert!(!outside.contains(Point3::new(1, 1, 1)));
+
+        let inside = set.flood_fill(Point3::new(1, 1, 1));
+        assert_eq!(inside.iter().collect::<Vec<_>>(), [Point3::new(1, 1, 1)]);
+    }
+
+    #[test]
+    fn test_voxel_set_full_range() {
+        let corners = [Point3::new(-100i8, 0, 0), Point3::new(100, 1, 1)];
+        let set = VoxelSet::from_points(corners, 0).unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), corners);
+        assert!(VoxelSet::from_points(corners, 30).is_err());
+        assert!(VoxelSet::from_points(corners, -1).is_err());
+        assert!(VoxelSet::from_points(corners, i8::MIN).is_err());
+    }
 }
@@ -0,0 +1,505 @@
+use std::{fmt::Write, time::Duration};
+
+use anyhow::{anyhow, Context};
+
+pub const TABLE_START: &str = "<!--- results table start --->";
+pub const TABLE_END: &str = "<!--- results table end --->";
+pub const SUMMARY_START: &str = "<!--- results summary start --->";
+pub const SUMMARY_END: &str = "<!--- results summary end --->";
+
+const MARKER: &str = "😔";
+const BUDGET: Duration = Duration::from_millis(100);
+
+/// Timings of a single day. Parts that have no timing are shown as `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayTimings {
+    pub day: u8,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+impl DayTimings {
+    fn slowest_part(&self) -> Option<Duration> {
+        self.part_one.max(self.part_two)
+    }
+
+    fn total(&self) -> Duration {
+        self.part_one.unwrap_or_default() + self.part_two.unwrap_or_default()
+    }
+
+    /// Rows with solutions over a millisecond get one marker, over 50 ms two and over 500 ms three.
+    fn markers(&self) -> usize {
+        match self.slowest_part() {
+            Some(d) if d > Duration::from_millis(500) => 3,
+            Some(d) if d > Duration::from_millis(50) => 2,
+            Some(d) if d > Duration::from_millis(1) => 1,
+            _ => 0,
+        }
+    }
+}
+
+struct Row {
+    timings: DayTimings,
+    notes: String,
+}
+
+/// Rewrites the results table and the summary paragraph of `readme` using `timings`.
+///
+/// Days missing from `timings` keep their previous timings. The notes column is kept as is,
+/// apart from the 😔 markers which are recalculated.
+pub fn update_readme(readme: &str, timings: &[DayTimings]) -> anyhow::Result<String> {
+    let (before, table, after) = split_section(readme, TABLE_START, TABLE_END)?;
+
+    let mut rows = parse_table(table)?;
+    for t in timings {
+        match rows.iter_mut().find(|r| r.timings.day == t.day) {
+            Some(row) => row.timings = t.clone(),
+            None => rows.push(Row {
+                timings: t.clone(),
+                notes: String::new(),
+            }),
+        }
+    }
+    rows.sort_by_key(|r| r.timings.day);
+
+    let readme = format!(
+        "{}{}\n{}{}",
+        before,
+        TABLE_START,
+        format_table(&rows),
+        after
+    );
+
+    let (before, _, after) = split_section(&readme, SUMMARY_START, SUMMARY_END)?;
+    let all_timings: Vec<_> = rows.into_iter().map(|r| r.timings).collect();
+    Ok(format!(
+        "{}{}\n{}{}",
+        before,
+        SUMMARY_START,
+        format_summary(&all_timings),
+        after
+    ))
+}
+
+/// Splits `s` to the parts before the start marker, between the markers and from the end marker on.
+fn split_section<'a>(
+    s: &'a str,
+    start: &str,
+    end: &str,
+) -> anyhow::Result<(&'a str, &'a str, &'a str)> {
+    let (before, rest) = s
+        .split_once(start)
+        .ok_or_else(|| anyhow!("no '{}' in readme", start))?;
+    let end_index = rest
+        .find(end)
+        .ok_or_else(|| anyhow!("no '{}' in readme", end))?;
+
+    Ok((
+        before,
+        rest[..end_index].trim_start_matches('\n'),
+        &rest[end_index..],
+    ))
+}
+
+fn parse_table(table: &str) -> anyhow::Result<Vec<Row>> {
+    table
+        .lines()
+        .skip(2)
+        .filter(|l| l.starts_with('|'))
+        .map(|l| parse_row(l).with_context(|| format!("invalid table row '{}'", l)))
+        .collect()
+}
+
+fn parse_row(line: &str) -> anyhow::Result<Row> {
+    let cells: Vec<_> = line
+        .trim()
+        .trim_matches('|')
+        .split('|')
+        .map(str::trim)
+        .collect();
+    if cells.len() != 5 {
+        return Err(anyhow!("expected 5 cells, got {}", cells.len()));
+    }
+
+    let day = cells[0]
+        .strip_prefix('[')
+        .and_then(|s| s.split_once(']'))
+        .ok_or_else(|| anyhow!("invalid day '{}'", cells[0]))?
+        .0
+        .parse()?;
+
+    let notes = cells[4].trim_start_matches(MARKER).trim();
+
+    Ok(Row {
+        timings: DayTimings {
+            day,
+            part_one: parse_duration(cells[2])?,
+            part_two: parse_duration(cells[3])?,
+        },
+        notes: if notes == "-" {
+            String::new()
+        } else {
+            notes.to_owned()
+        },
+    })
+}
+
+/// Parses durations in the format produced by `{:.2?}`, e.g. `33.44µs`. `-` means no timing.
+pub fn parse_duration(s: &str) -> anyhow::Result<Option<Duration>> {
+    if s == "-" {
+        return Ok(None);
+    }
+
+    let units = [("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)];
+    for (suffix, scale) in units {
+        if let Some(value) = s.strip_suffix(suffix) {
+            let value: f64 = value
+                .parse()
+                .with_context(|| format!("invalid duration '{}'", s))?;
+            return Ok(Some(Duration::from_secs_f64(value * scale)));
+        }
+    }
+
+    Err(anyhow!("invalid duration '{}'", s))
+}
+
+fn format_duration(d: Option<Duration>) -> String {
+    match d {
+        Some(d) => format!("{:.2?}", d),
+        None => "-".to_owned(),
+    }
+}
+
+/// Width of the text when rendered, counting emoji as two columns like editors do.
+fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| if c as u32 >= 0x1F000 { 2 } else { 1 })
+        .sum()
+}
+
+fn pad(s: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        s,
+        " ".repeat(width.saturating_sub(display_width(s)))
+    )
+}
+
+fn format_table(rows: &[Row]) -> String {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|r| {
+            let t = &r.timings;
+            let markers = MARKER.repeat(t.markers());
+            let notes = match (markers.is_empty(), r.notes.is_empty()) {
+                (true, true) => "-".to_owned(),
+                (true, false) => r.notes.clone(),
+                (false, true) => markers,
+                (false, false) => format!("{} {}", markers, r.notes),
+            };
+
+            [
+                format!("[{}](https://adventofcode.com/2022/day/{})", t.day, t.day),
+                format!("[{:02}.rs](src/bin/{:02}.rs)", t.day, t.day),
+                format_duration(t.part_one),
+                format_duration(t.part_two),
+                notes,
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Solution", "Part 1", "Part 2", "Notes"];
+    let mut widths = header.map(display_width);
+    for row in &cells {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(display_width(cell));
+        }
+    }
+
+    let mut table = String::new();
+    let [day_w, solution_w, one_w, two_w, notes_w] = widths;
+
+    writeln!(
+        table,
+        "| {:^day_w$} | {} | {:>one_w$} | {:>two_w$} | {} |",
+        header[0],
+        pad(header[1], solution_w),
+        header[2],
+        header[3],
+        pad(header[4], notes_w),
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "|:{}:|:{}|{}:|{}:|:{}|",
+        "-".repeat(day_w),
+        "-".repeat(solution_w + 1),
+        "-".repeat(one_w + 1),
+        "-".repeat(two_w + 1),
+        "-".repeat(notes_w + 1),
+    )
+    .unwrap();
+
+    for [day, solution, one, two, notes] in &cells {
+        writeln!(
+            table,
+            "| {:^day_w$} | {} | {:>one_w$} | {:>two_w$} | {} |",
+            day,
+            pad(solution, solution_w),
+            one,
+            two,
+            pad(notes, notes_w),
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+/// Writes the summary paragraph below the table, sorting the days by how they fit the budget.
+fn format_summary(timings: &[DayTimings]) -> String {
+    let total: Duration = timings.iter().map(|t| t.total()).sum();
+    let over_budget: Vec<_> = timings.iter().filter(|t| t.total() > BUDGET).collect();
+    let over_half_budget: Vec<_> = timings
+        .iter()
+        .filter(|t| t.total() > BUDGET / 2 && t.total() <= BUDGET)
+        .collect();
+    let rest: Vec<_> = timings.iter().filter(|t| t.total() <= BUDGET / 2).collect();
+    let rest_total: Duration = rest.iter().map(|t| t.total()).sum();
+    let over_second: Vec<_> = timings
+        .iter()
+        .filter(|t| t.total() > Duration::from_secs(1))
+        .collect();
+    let plural = |days: &[&DayTimings], one: &'static str, many: &'static str| {
+        if days.len() > 1 {
+            many
+        } else {
+            one
+        }
+    };
+
+    let mut summary = String::new();
+    match (over_budget.is_empty(), over_half_budget.is_empty()) {
+        (false, false) => writeln!(
+            summary,
+            "In the end, {} blew the 100 ms budget by {} while {} {} over 50 ms.",
+            day_list(&over_budget),
+            plural(&over_budget, "itself", "themselves"),
+            day_list(&over_half_budget),
+            plural(&over_half_budget, "was", "were"),
+        ),
+        (false, true) => writeln!(
+            summary,
+            "In the end, {} blew the 100 ms budget by {}.",
+            day_list(&over_budget),
+            plural(&over_budget, "itself", "themselves"),
+        ),
+        (true, false) => writeln!(
+            summary,
+            "In the end, no day blew the 100 ms budget by itself, but {} {} over 50 ms.",
+            day_list(&over_half_budget),
+            plural(&over_half_budget, "was", "were"),
+        ),
+        (true, true) => writeln!(summary, "In the end, every day stayed under 50 ms."),
+    }
+    .unwrap();
+
+    if !rest.is_empty() && rest.len() < timings.len() {
+        let days = match rest.len() {
+            1 => "the remaining day".to_owned(),
+            n => format!("the rest of the {} days", n),
+        };
+        let verdict = if rest_total <= BUDGET {
+            ", which is pretty decent"
+        } else {
+            ""
+        };
+        writeln!(
+            summary,
+            "Ignoring those outliers, the total time for {} is {}{}.",
+            days,
+            format_total(rest_total),
+            verdict
+        )
+        .unwrap();
+    }
+
+    match over_second.iter().map(|t| t.total()).max() {
+        None => writeln!(summary, "No day exceeded total runtime of one second."),
+        Some(slowest) => writeln!(
+            summary,
+            "Only {} exceeded total runtime of one second{}.",
+            day_list(&over_second),
+            match (slowest < Duration::from_secs(2), over_second.len() > 1) {
+                (true, false) => " and even that is under two seconds",
+                (true, true) => " and even those are under two seconds",
+                (false, _) => "",
+            }
+        ),
+    }
+    .unwrap();
+
+    let ratio = total.as_secs_f64() / BUDGET.as_secs_f64();
+    if total > BUDGET {
+        writeln!(
+            summary,
+            "That said, the total runtime for all days is {}, which is {:.1} times the budgeted 100 ms.",
+            format_total(total),
+            ratio
+        )
+    } else {
+        writeln!(
+            summary,
+            "The total runtime for all days is {}, which fits in the budgeted 100 ms.",
+            format_total(total)
+        )
+    }
+    .unwrap();
+    summary
+}
+
+fn day_list(timings: &[&DayTimings]) -> String {
+    let days: Vec<_> = timings.iter().map(|t| t.day.to_string()).collect();
+    match days.split_last() {
+        Some((last, [])) => format!("day {}", last),
+        Some((last, rest)) => format!("days {} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+fn format_total(d: Duration) -> String {
+    if d >= Duration::from_secs(1) {
+        format!("{:.2} seconds", d.as_secs_f64())
+    } else {
+        format!("{:.0} ms", d.as_secs_f64() * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readme(table: &str, summary: &str) -> String {
+        format!(
+            "# Title\n\n{}\n{}{}\n\n{}\n{}\n{}\n\n## Footer\n",
+            TABLE_START, table, TABLE_END, SUMMARY_START, summary, SUMMARY_END
+        )
+    }
+
+    const TABLE: &str = "\
+|                    Day                     | Solution               |   Part 1 |   Part 2 | Notes             |
+|:------------------------------------------:|:-----------------------|---------:|---------:|:------------------|
+|  [1](https://adventofcode.com/2022/day/1)  | [01.rs](src/bin/01.rs) |  33.44µs |  31.10µs | Single iteration  |
+|  [2](https://adventofcode.com/2022/day/2)  | [02.rs](src/bin/02.rs) |  76.84µs |   5.42ms | 😔                |
+| [19](https://adventofcode.com/2022/day/19) | [19.rs](src/bin/19.rs) | 574.06ms | 941.26ms | 😔😔😔 Heuristics |
+";
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("33.44µs").unwrap(),
+            Some(Duration::from_nanos(33440))
+        );
+        assert_eq!(
+            parse_duration("1.50s").unwrap(),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse_duration("-").unwrap(), None);
+        assert!(parse_duration("fast").is_err());
+    }
+
+    #[test]
+    fn test_unchanged_table_is_kept() {
+        let original = readme(TABLE, "old summary");
+        let updated = update_readme(&original, &[]).unwrap();
+        assert!(updated.contains(TABLE));
+        assert!(!updated.contains("old summary"));
+        assert!(updated.ends_with(&format!("{}\n\n## Footer\n", SUMMARY_END)));
+    }
+
+    #[test]
+    fn test_markers_and_notes() {
+        let updated = update_readme(
+            &readme(TABLE, ""),
+            &[
+                DayTimings {
+                    day: 2,
+                    part_one: Some(Duration::from_micros(70)),
+                    part_two: Some(Duration::from_micros(80)),
+                },
+                DayTimings {
+                    day: 1,
+                    part_one: Some(Duration::from_millis(60)),
+                    part_two: None,
+                },
+            ],
+        )
+        .unwrap();
+
+        assert!(updated.contains(
+            "|  [1](https://adventofcode.com/2022/day/1)  | [01.rs](src/bin/01.rs) |  60.00ms |        - | 😔😔 Single iteration |"
+        ));
+        assert!(updated.contains(
+            "|  [2](https://adventofcode.com/2022/day/2)  | [02.rs](src/bin/02.rs) |  70.00µs |  80.00µs | -                     |"
+        ));
+        assert!(updated.contains("| 😔😔😔 Heuristics     |"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let ms = |ms| Some(Duration::from_millis(ms));
+        let timings = [
+            DayTimings {
+                day: 1,
+                part_one: ms(10),
+                part_two: ms(20),
+            },
+            DayTimings {
+                day: 15,
+                part_one: ms(1),
+                part_two: ms(122),
+            },
+            DayTimings {
+                day: 19,
+                part_one: ms(574),
+                part_two: ms(941),
+            },
+            DayTimings {
+                day: 20,
+                part_one: ms(5),
+                part_two: ms(53),
+            },
+        ];
+
+        assert_eq!(
+            format_summary(&timings),
+            "In the end, days 15 and 19 blew the 100 ms budget by themselves while day 20 was over 50 ms.\n\
+             Ignoring those outliers, the total time for the remaining day is 30 ms, which is pretty decent.\n\
+             Only day 19 exceeded total runtime of one second and even that is under two seconds.\n\
+             That said, the total runtime for all days is 1.73 seconds, which is 17.3 times the budgeted 100 ms.\n"
+        );
+
+        let fast = [
+            timings[0].clone(),
+            DayTimings {
+                day: 2,
+                part_one: ms(1),
+                part_two: ms(1),
+            },
+        ];
+        assert_eq!(
+            format_summary(&fast),
+            "In the end, every day stayed under 50 ms.\n\
+             No day exceeded total runtime of one second.\n\
+             The total runtime for all days is 32 ms, which fits in the budgeted 100 ms.\n"
+        );
+        let close = [&fast[..], &timings[3..]].concat();
+        assert_eq!(
+            format_summary(&close),
+            "In the end, no day blew the 100 ms budget by itself, but day 20 was over 50 ms.\n\
+             Ignoring those outliers, the total time for the rest of the 2 days is 32 ms, which is pretty decent.\n\
+             No day exceeded total runtime of one second.\n\
+             The total runtime for all days is 90 ms, which fits in the budgeted 100 ms.\n"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::expected_answer,
+    bench::{BenchConfig, BenchStats},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Name of the environment variable selecting the file where `solve!` appends its records.
+pub const RECORD_FILE_ENV: &str = "AOC_RECORD_FILE";
+
+/// Machine-readable result of running a single part of a day.
+///
+/// Records are written as JSON lines so that the runner (or any other tool) can consume
+/// exact timings instead of scraping the human-readable output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Error the solver failed with, including its context chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time taken by the part. In bench mode this is the median of all samples.
+    pub elapsed_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<BenchStats>,
+    /// Correct answer from `src/answers`, filled in by [`Record::verify`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+}
+
+/// What a solver produced: an answer, nothing yet, or an error.
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(String),
+    NotSolved,
+    Failed(anyhow::Error),
+}
+
+/// Return types accepted from solvers.
+///
+/// Answers can be returned as is or wrapped in `Option`, where `None` means that the part
+/// has not been solved yet. Both can be further wrapped in a `Result` to report failures.
+pub trait Answer {
+    fn into_outcome(self) -> Outcome;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_outcome(self) -> Outcome {
+                    Outcome::Solved(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str);
+
+impl<T: Display> Answer for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::NotSolved,
+        }
+    }
+}
+
+impl<T: Answer, E: Into<anyhow::Error>> Answer for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => answer.into_outcome(),
+            Err(e) => Outcome::Failed(e.into()),
+        }
+    }
+}
+
+impl Record {
+    fn new(day: u8, part: u8, outcome: Outcome, elapsed_ns: u64) -> Record {
+        let (answer, error) = match outcome {
+            Outcome::Solved(answer) => (Some(answer), None),
+            Outcome::NotSolved => (None, None),
+            // alternate formatting prints the whole context chain on a single line.
+            Outcome::Failed(e) => (None, Some(format!("{:#}", e))),
+        };
+
+        Record {
+            day,
+            part,
+            answer,
+            error,
+            elapsed_ns,
+            stats: None,
+            expected: None,
+        }
+    }
+
+    /// Record for a part that did not produce an outcome at all, e.g. because it panicked.
+    pub fn failed(day: u8, part: u8, error: &str) -> Record {
+        Record::new(day, part, Outcome::Failed(anyhow!("{}", error)), 0)
+    }
+
+    pub fn measure<R: Answer>(
+        day: u8,
+        part: u8,
+        solver: impl FnOnce(&str) -> R,
+        input: &str,
+    ) -> Record {
+        let timer = Instant::now();
+        let result = solver(input);
+        let elapsed = timer.elapsed();
+
+        Record::new(day, part, result.into_outcome(), elapsed.as_nanos() as u64)
+    }
+
+    /// Runs the solver repeatedly as described by `config` and records the statistics.
+    pub fn bench<R: Answer>(
+        day: u8,
+        part: u8,
+        solver: impl Fn(&str) -> R,
+        input: &str,
+        config: &BenchConfig,
+    ) -> Record {
+        let started = Instant::now();
+
+        for _ in 0..config.warmup {
+            if started.elapsed() >= config.budget {
+                break;
+            }
+            solver(input);
+        }
+
+        let mut samples = Vec::with_capacity(config.samples as usize);
+        let mut result = None;
+        while samples.len() < config.samples as usize
+            && (samples.is_empty() || started.elapsed() < config.budget)
+        {
+            let timer = Instant::now();
+            let sample = solver(input);
+            samples.push(timer.elapsed().as_nanos() as u64);
+            result = Some(sample);
+        }
+
+        let stats = BenchStats::from_samples(&mut samples);
+        let outcome = result.map_or(Outcome::NotSolved, Answer::into_outcome);
+        Record {
+            stats: Some(stats.clone()),
+            ..Record::new(day, part, outcome, stats.median_ns)
+        }
+    }
+
+    /// Benchmarks the solver if `bench` is given, otherwise measures a single run.
+    pub fn run<R: Answer>(
+        day: u8,
+        part: u8,
+        solver: impl Fn(&str) -> R,
+        input: &str,
+        bench: Option<&BenchConfig>,
+    ) -> Record {
+        match bench {
+            Some(config) => Record::bench(day, part, solver, input, config),
+            None => Record::measure(day, part, solver, input),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_ns)
+    }
+
+    /// Looks up the correct answer for the part so that the record can be verified.
+    pub fn verify(&mut self) {
+        self.expected = expected_answer(self.day, self.part);
+    }
+
+    /// Whether the answer matches the correct one, or `None` if there is nothing to compare.
+    /// A part without an answer is not correct when one is expected.
+    pub fn is_correct(&self) -> Option<bool> {
+        match (&self.answer, &self.expected) {
+            (Some(answer), Some(expected)) => Some(answer.trim() == expected),
+            (None, Some(_)) => Some(false),
+            (_, None) => None,
+        }
+    }
+
+    fn verification_mark(&self) -> String {
+        match (self.is_correct(), &self.expected) {
+            (Some(true), _) => " ✅".to_owned(),
+            (Some(false), Some(expected)) => format!(" ❌ (expected {})", expected),
+            _ => String::new(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, self.part, ANSI_RESET);
+        match (&self.answer, &self.stats) {
+            (Some(answer), Some(stats)) => {
+                println!(
+                    "{} {}(median: {:.2?}, min: {:.2?}, mean: {:.2?} ± {:.2?}, {} samples){}{}",
+                    answer,
+                    ANSI_ITALIC,
+                    stats.median(),
+                    stats.min(),
+                    stats.mean(),
+                    stats.stddev(),
+                    stats.samples,
+                    ANSI_RESET,
+                    self.verification_mark()
+                );
+            }
+            (Some(answer), None) => {
+                println!(
+                    "{} {}(elapsed: {:.2?}){}{}",
+                    answer,
+                    ANSI_ITALIC,
+                    self.elapsed(),
+                    ANSI_RESET,
+                    self.verification_mark()
+                );
+            }
+            (None, _) => match &self.error {
+                Some(error) => println!("failed: {}", error),
+                None => println!("not solved.{}", self.verification_mark()),
+            },
+        }
+    }
+
+    /// Appends the record to the file named by [`RECORD_FILE_ENV`], if the variable is set.
+    pub fn append_to_env_file(&self) -> io::Result<()> {
+        match env::var_os(RECORD_FILE_ENV) {
+            Some(path) => self.append_to(Path::new(&path)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn append_to(&self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(self)?)
+    }
+}
+
+pub fn read_records(path: &Path) -> anyhow::Result<Vec<Record>> {
+    parse_records(&fs::read_to_string(path)?)
+}
+
+pub fn parse_records(s: &str) -> anyhow::Result<Vec<Record>> {
+    s.lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| Ok(serde_json::from_str(l)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_records_round_trip() {
+        let records = vec![
+            Record {
+                day: 10,
+                part: 2,
+                answer: Some("##..\n..##\n".to_owned()),
+                error: None,
+                elapsed_ns: 742,
+                stats: None,
+                expected: None,
+            },
+            Record {
+                day: 25,
+                part: 1,
+                answer: None,
+                error: Some("line 3: invalid direction 'X'".to_owned()),
+                elapsed_ns: 0,
+                stats: Some(BenchStats::from_samples(&mut [1, 2, 3])),
+                expected: Some("42".to_owned()),
+            },
+        ];
+
+        let lines: String = records
+            .iter()
+            .map(|r| serde_json::to_string(r).unwrap() + "\n")
+            .collect();
+
+        assert_eq!(lines.lines().count(), 2);
+        assert_eq!(parse_records(&lines).unwrap(), records);
+    }
+
+    #[test]
+    fn test_outcome_of_answer_types() {
+        assert!(matches!(42.into_outcome(), Outcome::Solved(a) if a == "42"));
+        assert!(matches!(None::<u32>.into_outcome(), Outcome::NotSolved));
+
+        let ok: anyhow::Result<Option<u32>> = Ok(Some(7));
+        assert!(matches!(ok.into_outcome(), Outcome::Solved(a) if a == "7"));
+
+        let err: anyhow::Result<u32> = Err(anyhow!("invalid direction 'X'")).context("line 3");
+        let record = Record::new(1, 1, err.into_outcome(), 0);
+        assert_eq!(record.answer, None);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("line 3: invalid direction 'X'")
+        );
+    }
+
+    #[test]
+    fn test_is_correct() {
+        let record = |answer: Option<&str>, expected: Option<&str>| Record {
+            day: 1,
+            part: 1,
+            answer: answer.map(str::to_owned),
+            error: None,
+            elapsed_ns: 0,
+            stats: None,
+            expected: expected.map(str::to_owned),
+        };
+        assert_eq!(record(Some("42\n"), Some("42")).is_correct(), Some(true));
+        assert_eq!(record(Some("41"), Some("42")).is_correct(), Some(false));
+        assert_eq!(record(None, Some("42")).is_correct(), Some(false));
+        assert_eq!(record(Some("42"), None).is_correct(), None);
+        assert_eq!(record(None, None).is_correct(), None);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, IsTerminal, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    grid::{Grid, InfiniteGrid},
+    point::Point,
+    ANSI_RESET,
+};
+
+/// Name of the environment variable enabling animations. A numeric value sets the frame delay
+/// in milliseconds.
+pub const ANIMATE_ENV: &str = "AOC_ANIMATE";
+
+/// Colours are disabled when this environment variable is set, see <https://no-color.org>.
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    /// The escape sequence setting this as the foreground colour.
+    pub fn ansi(self) -> &'static str {
+        match self {
+            Color::Black => "\x1b[30m",
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::White => "\x1b[37m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+
+    /// The colour as red, green and blue components, for image export.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0, 0, 0],
+            Color::Red => [205, 49, 49],
+            Color::Green => [13, 188, 121],
+            Color::Yellow => [229, 229, 16],
+            Color::Blue => [36, 114, 200],
+            Color::Magenta => [188, 63, 188],
+            Color::Cyan => [17, 168, 205],
+            Color::White => [229, 229, 229],
+            Color::Gray => [102, 102, 102],
+        }
+    }
+
+    /// The colour in SVG notation, e.g. `#cd3131`.
+    pub fn hex(self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+/// How a single cell is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    pub symbol: char,
+    pub color: Option<Color>,
+}
+
+impl Glyph {
+    pub const fn plain(symbol: char) -> Glyph {
+        Glyph {
+            symbol,
+            color: None,
+        }
+    }
+
+    pub const fn colored(symbol: char, color: Color) -> Glyph {
+        Glyph {
+            symbol,
+            color: Some(color),
+        }
+    }
+}
+
+/// Cell types that know how to draw themselves, so that grids of them are a [`Canvas`].
+pub trait Draw {
+    fn glyph(&self) -> Glyph;
+}
+
+impl Draw for char {
+    fn glyph(&self) -> Glyph {
+        Glyph::plain(*self)
+    }
+}
+
+impl Draw for u8 {
+    fn glyph(&self) -> Glyph {
+        Glyph::plain(*self as char)
+    }
+}
+
+impl Draw for bool {
+    fn glyph(&self) -> Glyph {
+        Glyph::plain(if *self { '#' } else { '.' })
+    }
+}
+
+/// Anything that can be drawn as a rectangle of glyphs, with `y` growing downwards.
+pub trait Canvas {
+    /// Inclusive corners of the area to draw, or `None` if there is nothing to draw.
+    fn extent(&self) -> Option<(Point<i64>, Point<i64>)>;
+
+    fn glyph(&self, p: Point<i64>) -> Glyph;
+}
+
+impl<T: Draw> Canvas for Grid<T> {
+    fn extent(&self) -> Option<(Point<i64>, Point<i64>)> {
+        (!self.is_empty()).then(|| {
+            let max = Point::new(self.width() as i64 - 1, self.height() as i64 - 1);
+            (Point::new(0, 0), max)
+        })
+    }
+
+    fn glyph(&self, p: Point<i64>) -> Glyph {
+        self[p].glyph()
+    }
+}
+
+/// Draws the cells written so far.
+impl<T: Draw + Clone> Canvas for InfiniteGrid<T> {
+    fn extent(&self) -> Option<(Point<i64>, Point<i64>)> {
+        self.bounds()
+    }
+
+    fn glyph(&self, p: Point<i64>) -> Glyph {
+        self[p].glyph()
+    }
+}
+
+/// A canvas drawing the given area with a function, for structures without cells.
+pub struct FnCanvas<F> {
+    min: Point<i64>,
+    max: Point<i64>,
+    glyph: F,
+}
+
+impl<F: Fn(Point<i64>) -> Glyph> FnCanvas<F> {
+    pub fn new(min: Point<i64>, max: Point<i64>, glyph: F) -> Self {
+        FnCanvas { min, max, glyph }
+    }
+}
+
+impl<F: Fn(Point<i64>) -> Glyph> Canvas for FnCanvas<F> {
+    fn extent(&self) -> Option<(Point<i64>, Point<i64>)> {
+        Some((self.min, self.max))
+    }
+
+    fn glyph(&self, p: Point<i64>) -> Glyph {
+        (self.glyph)(p)
+    }
+}
+
+/// Whether stdout is a terminal and colours have not been disabled with [`NO_COLOR_ENV`].
+pub fn colors_enabled() -> bool {
+    env::var_os(NO_COLOR_ENV).is_none() && io::stdout().is_terminal()
+}
+
+/// Draws the canvas as lines of text, with ANSI colours if `colors` is set.
+pub fn render(canvas: &impl Canvas, colors: bool) -> String {
+    let mut out = String::new();
+    let (min, max) = match canvas.extent() {
+        Some(extent) => extent,
+        None => return out,
+    };
+
+    for y in min.y..=max.y {
+        let mut current = None;
+        for x in min.x..=max.x {
+            let glyph = canvas.glyph(Point::new(x, y));
+            if colors && glyph.color != current {
+                out.push_str(glyph.color.map_or(ANSI_RESET, Color::ansi));
+                current = glyph.color;
+            }
+            out.push(glyph.symbol);
+        }
+        if current.is_some() {
+            out.push_str(ANSI_RESET);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Draws the frames of a simulation in place in the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    /// Pause after each drawn frame.
+    pub delay: Duration,
+    /// Only every `every`th frame is drawn, to speed up long simulations.
+    pub every: u32,
+    pub colors: bool,
+    frame: u64,
+}
+
+impl Animation {
+    pub fn new(delay: Duration) -> Animation {
+        Animation {
+            delay,
+            every: 1,
+            colors: colors_enabled(),
+            frame: 0,
+        }
+    }
+
+    /// Reads the animation settings from the command line and [`ANIMATE_ENV`].
+    pub fn from_env() -> Option<Animation> {
+        let mut args = pico_args::Arguments::from_env();
+        Animation::from_args(&mut args).unwrap_or_else(|e| {
+            eprintln!("invalid animation arguments: {}", e);
+            None
+        })
+    }
+
+    /// Consumes `--animate`, `--frame-delay <ms>` and `--frame-every <n>` from `args`.
+    pub fn from_args(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Animation>, pico_args::Error> {
+        let flag = args.contains("--animate");
+        let delay: Option<u64> = args.opt_value_from_str("--frame-delay")?;
+        let every: Option<u32> = args.opt_value_from_str("--frame-every")?;
+        let env = env::var(ANIMATE_ENV).ok();
+
+        // `--frame-every` alone does not enable animations, as it is shared with frame export
+        if !flag && delay.is_none() && env.is_none() {
+            return Ok(None);
+        }
+
+        let delay = delay
+            .or_else(|| env.and_then(|v| v.parse().ok()))
+            .unwrap_or(50);
+        let mut animation = Animation::new(Duration::from_millis(delay));
+        animation.every = every.unwrap_or(1).max(1);
+        Ok(Some(animation))
+    }
+
+    /// Draws the next frame over the previous one and waits for the frame delay.
+    ///
+    /// Frames are skipped according to [`Animation::every`].
+    pub fn frame(&mut self, canvas: &impl Canvas, caption: &str) {
+        self.frame += 1;
+        if (self.frame - 1).is_multiple_of(self.every as u64) {
+            self.draw(canvas, caption);
+            thread::sleep(self.delay);
+        }
+    }
+
+    /// Draws the last frame of the simulation, even if it would be skipped.
+    pub fn finish(&mut self, canvas: &impl Canvas, caption: &str) {
+        self.draw(canvas, caption);
+    }
+
+    fn draw(&self, canvas: &impl Canvas, caption: &str) {
+        let mut out = String::new();
+        // overwrite the previous frame from the top left instead of clearing the whole screen
+        // first, which flickers, and only clear what is left of it afterwards
+        out.push_str("\x1b[H");
+        for line in render(canvas, self.colors).lines() {
+            let _ = writeln!(out, "{}\x1b[K", line);
+        }
+        let _ = writeln!(out, "{}\x1b[K", caption);
+        out.push_str("\x1b[J");
+
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_grid() {
+        let grid: Grid<char> = "#.\n.#".parse().unwrap();
+        assert_eq!(render(&grid, false), "#.\n.#\n");
+
+        let mut sparse = InfiniteGrid::<bool>::new();
+        sparse.set(Point::new(-1, 5), true);
+        sparse.set(Point::new(1, 6), true);
+        assert_eq!(render(&sparse, false), "#..\n..#\n");
+    }
+
+    #[test]
+    fn test_render_colors() {
+        let canvas = FnCanvas::new(Point::new(0, 0), Point::new(2, 0), |p| match p.x {
+            0 => Glyph::plain('.'),
+            _ => Glyph::colored('o', Color::Red),
+        });
+        assert_eq!(render(&canvas, false), ".oo\n");
+        assert_eq!(
+            render(&canvas, true),
+            format!(".{}oo{}\n", Color::Red.ansi(), ANSI_RESET)
+        );
+    }
+}
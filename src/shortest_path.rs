@@ -1,31 +1,211 @@
 use std::cmp::Reverse;
+use std::collections::VecDeque;
 use std::hash::Hash;
+use std::iter;
+use std::ops::Add;
 
 use hashbrown::HashMap;
+use num::{Bounded, One, Zero};
 use priority_queue::PriorityQueue;
 
+/// Numeric type of edge costs and path lengths.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Zero + One + Bounded {}
+
+impl<T> Cost for T where T: Copy + Ord + Add<Output = T> + Zero + One + Bounded {}
+
 pub trait Graph {
     type Node: Eq + Hash + Clone;
+    type Cost: Cost;
 
     fn is_solution(&self, node: &Self::Node) -> bool;
-    fn collect_neighbors(&self, node: &Self::Node, neighbors: &mut Vec<(Self::Node, u32)>);
-    fn heuristic_distance(&self, node: &Self::Node) -> u32;
+    fn collect_neighbors(&self, node: &Self::Node, neighbors: &mut Vec<(Self::Node, Self::Cost)>);
+
+    /// Estimated cost from `node` to the closest solution. Without a heuristic, [`a_star`]
+    /// behaves like [`dijkstra`].
+    fn heuristic_distance(&self, _node: &Self::Node) -> Self::Cost {
+        Self::Cost::zero()
+    }
 }
 
-pub fn shortest_path_len<G>(g: &G, start: G::Node) -> Option<(G::Node, u32)>
+/// Best known cost from the start to each node during a search.
+pub trait ScoreStore<N, C> {
+    fn score(&self, node: &N) -> Option<C>;
+    fn set_score(&mut self, node: &N, score: C);
+}
+
+impl<N, C> ScoreStore<N, C> for HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy,
+{
+    fn score(&self, node: &N) -> Option<C> {
+        self.get(node).copied()
+    }
+
+    fn set_score(&mut self, node: &N, score: C) {
+        self.insert(node.clone(), score);
+    }
+}
+
+/// Score store backed by a vector, for nodes that map to small, dense indices.
+pub struct DenseScores<C, F> {
+    scores: Vec<C>,
+    index: F,
+}
+
+impl<C: Cost, F> DenseScores<C, F> {
+    /// Creates a store for nodes whose `index` is below `len`.
+    pub fn new(len: usize, index: F) -> Self {
+        DenseScores {
+            scores: vec![C::max_value(); len],
+            index,
+        }
+    }
+}
+
+impl<N, C, F> ScoreStore<N, C> for DenseScores<C, F>
+where
+    C: Cost,
+    F: Fn(&N) -> usize,
+{
+    fn score(&self, node: &N) -> Option<C> {
+        let score = self.scores[(self.index)(node)];
+        (score != C::max_value()).then_some(score)
+    }
+
+    fn set_score(&mut self, node: &N, score: C) {
+        self.scores[(self.index)(node)] = score;
+    }
+}
+
+/// Counters collected while searching.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Nodes taken from the open set.
+    pub expanded: usize,
+    /// Nodes added to the open set, including priority updates of nodes already in it.
+    pub pushed: usize,
+    pub max_open_set: usize,
+    /// Nodes added to the open set again after they had been expanded.
+    pub reopened: usize,
+}
+
+/// Outcome of a search that keeps track of how each node was reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    pub solution: Option<(N, C)>,
+    /// Nodes from the start to the solution, both included. Empty without a solution.
+    pub path: Vec<N>,
+    pub stats: SearchStats,
+}
+
+/// Finds the solution closest to any of the `starts` using A* and returns it with its distance.
+pub fn a_star<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> Option<(G::Node, G::Cost)>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut stats = SearchStats::default();
+    search(
+        g,
+        starts,
+        scores,
+        |node| g.heuristic_distance(node),
+        None,
+        &mut stats,
+    )
+}
+
+/// Like [`a_star`], but also returns the path to the solution and the search statistics.
+pub fn a_star_with_path<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> SearchResult<G::Node, G::Cost>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut came_from = HashMap::new();
+    let mut stats = SearchStats::default();
+    let solution = search(
+        g,
+        starts,
+        scores,
+        |node| g.heuristic_distance(node),
+        Some(&mut came_from),
+        &mut stats,
+    );
+    SearchResult::new(solution, &came_from, stats)
+}
+
+/// Like [`a_star`], but ignores the heuristic of the graph.
+pub fn dijkstra<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> Option<(G::Node, G::Cost)>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut stats = SearchStats::default();
+    search(g, starts, scores, |_| G::Cost::zero(), None, &mut stats)
+}
+
+/// Like [`dijkstra`], but also returns the path to the solution and the search statistics.
+pub fn dijkstra_with_path<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> SearchResult<G::Node, G::Cost>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut came_from = HashMap::new();
+    let mut stats = SearchStats::default();
+    let solution = search(
+        g,
+        starts,
+        scores,
+        |_| G::Cost::zero(),
+        Some(&mut came_from),
+        &mut stats,
+    );
+    SearchResult::new(solution, &came_from, stats)
+}
+
+fn search<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut scores: S,
+    heuristic: impl Fn(&G::Node) -> G::Cost,
+    mut came_from: Option<&mut HashMap<G::Node, G::Node>>,
+    stats: &mut SearchStats,
+) -> Option<(G::Node, G::Cost)>
 where
     G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
 {
-    let mut g_score = HashMap::<G::Node, u32>::new();
-    let mut open_set = PriorityQueue::<G::Node, Reverse<u32>>::new();
+    let mut open_set = PriorityQueue::<G::Node, Reverse<G::Cost>>::new();
     let mut neighbors = Vec::new();
 
-    g_score.insert(start.clone(), 0);
-    let start_distance = g.heuristic_distance(&start);
-    open_set.push(start, Reverse(start_distance));
+    for start in starts {
+        scores.set_score(&start, G::Cost::zero());
+        let start_distance = heuristic(&start);
+        open_set.push(start, Reverse(start_distance));
+        stats.pushed += 1;
+    }
+    stats.max_open_set = open_set.len();
 
     while let Some((current, _)) = open_set.pop() {
-        let current_gscore = *g_score.get(&current).unwrap();
+        stats.expanded += 1;
+        let current_gscore = scores.score(&current).unwrap();
 
         if g.is_solution(&current) {
             return Some((current, current_gscore));
@@ -34,14 +214,190 @@ where
         g.collect_neighbors(&current, &mut neighbors);
         for (neighbor, cost) in neighbors.drain(..) {
             let tentative_gscore = current_gscore + cost;
-            if tentative_gscore < g_score.get(&neighbor).copied().unwrap_or(u32::MAX) {
-                g_score.insert(neighbor.clone(), tentative_gscore);
+            let previous_gscore = scores.score(&neighbor);
+            if previous_gscore.is_none_or(|score| tentative_gscore < score) {
+                scores.set_score(&neighbor, tentative_gscore);
+                if let Some(came_from) = came_from.as_mut() {
+                    came_from.insert(neighbor.clone(), current.clone());
+                }
 
-                let neighbor_score = tentative_gscore + g.heuristic_distance(&neighbor);
-                open_set.push(neighbor, Reverse(neighbor_score));
+                let neighbor_score = tentative_gscore + heuristic(&neighbor);
+                let was_open = open_set.push(neighbor, Reverse(neighbor_score)).is_some();
+                stats.pushed += 1;
+                if previous_gscore.is_some() && !was_open {
+                    stats.reopened += 1;
+                }
             }
         }
+        stats.max_open_set = stats.max_open_set.max(open_set.len());
     }
 
     None
 }
+
+/// Breadth-first search for graphs where every edge costs one. Edge costs are ignored.
+pub fn bfs<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> Option<(G::Node, G::Cost)>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut stats = SearchStats::default();
+    breadth_first(g, starts, scores, None, &mut stats)
+}
+
+/// Like [`bfs`], but also returns the path to the solution and the search statistics.
+pub fn bfs_with_path<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    scores: S,
+) -> SearchResult<G::Node, G::Cost>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut came_from = HashMap::new();
+    let mut stats = SearchStats::default();
+    let solution = breadth_first(g, starts, scores, Some(&mut came_from), &mut stats);
+    SearchResult::new(solution, &came_from, stats)
+}
+
+fn breadth_first<G, S>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut scores: S,
+    mut came_from: Option<&mut HashMap<G::Node, G::Node>>,
+    stats: &mut SearchStats,
+) -> Option<(G::Node, G::Cost)>
+where
+    G: Graph,
+    S: ScoreStore<G::Node, G::Cost>,
+{
+    let mut queue = VecDeque::new();
+    let mut neighbors = Vec::new();
+
+    for start in starts {
+        if scores.score(&start).is_none() {
+            scores.set_score(&start, G::Cost::zero());
+            queue.push_back((start, G::Cost::zero()));
+            stats.pushed += 1;
+        }
+    }
+    stats.max_open_set = queue.len();
+
+    while let Some((current, distance)) = queue.pop_front() {
+        stats.expanded += 1;
+        if g.is_solution(&current) {
+            return Some((current, distance));
+        }
+
+        g.collect_neighbors(&current, &mut neighbors);
+        for (neighbor, _) in neighbors.drain(..) {
+            if scores.score(&neighbor).is_none() {
+                scores.set_score(&neighbor, distance + G::Cost::one());
+                if let Some(came_from) = came_from.as_mut() {
+                    came_from.insert(neighbor.clone(), current.clone());
+                }
+                queue.push_back((neighbor, distance + G::Cost::one()));
+                stats.pushed += 1;
+            }
+        }
+        stats.max_open_set = stats.max_open_set.max(queue.len());
+    }
+
+    None
+}
+
+impl<N: Eq + Hash + Clone, C> SearchResult<N, C> {
+    fn new(solution: Option<(N, C)>, came_from: &HashMap<N, N>, stats: SearchStats) -> Self {
+        let mut path = Vec::new();
+        if let Some((goal, _)) = &solution {
+            let mut node = goal;
+            path.push(node.clone());
+            while let Some(previous) = came_from.get(node) {
+                path.push(previous.clone());
+                node = previous;
+            }
+            path.reverse();
+        }
+
+        SearchResult {
+            solution,
+            path,
+            stats,
+        }
+    }
+}
+
+pub fn shortest_path_len<G>(g: &G, start: G::Node) -> Option<(G::Node, G::Cost)>
+where
+    G: Graph,
+{
+    a_star(g, iter::once(start), HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted line 0 - 1 - ... - 9, where stepping onto an even node costs 3.
+    struct Line;
+
+    impl Graph for Line {
+        type Node = u8;
+        type Cost = u32;
+
+        fn is_solution(&self, node: &u8) -> bool {
+            *node == 9
+        }
+
+        fn collect_neighbors(&self, node: &u8, neighbors: &mut Vec<(u8, u32)>) {
+            for n in [node.wrapping_sub(1), node + 1] {
+                if n < 10 {
+                    neighbors.push((n, if n % 2 == 0 { 3 } else { 1 }));
+                }
+            }
+        }
+
+        fn heuristic_distance(&self, node: &u8) -> u32 {
+            9 - *node as u32
+        }
+    }
+
+    #[test]
+    fn test_searches_agree() {
+        let dense = || DenseScores::new(10, |n: &u8| *n as usize);
+
+        assert_eq!(shortest_path_len(&Line, 0), Some((9, 17)));
+        assert_eq!(a_star(&Line, [0], dense()), Some((9, 17)));
+        assert_eq!(dijkstra(&Line, [0], dense()), Some((9, 17)));
+        assert_eq!(bfs(&Line, [0], dense()), Some((9, 9)));
+    }
+
+    #[test]
+    fn test_multiple_starts() {
+        let dense = DenseScores::new(10, |n: &u8| *n as usize);
+        assert_eq!(a_star(&Line, [0, 6, 3], dense), Some((9, 5)));
+        assert_eq!(bfs(&Line, [2, 7], HashMap::new()), Some((9, 2)));
+    }
+
+    #[test]
+    fn test_path_and_stats() {
+        let result = a_star_with_path(&Line, [0, 6], HashMap::new());
+        assert_eq!(result.solution, Some((9, 5)));
+        assert_eq!(result.path, vec![6, 7, 8, 9]);
+        assert!(result.stats.expanded >= result.path.len());
+        assert_eq!(result.stats.reopened, 0);
+        assert!(result.stats.pushed >= result.stats.expanded);
+        assert!(result.stats.max_open_set >= 2);
+
+        let result = bfs_with_path(&Line, [7], HashMap::new());
+        assert_eq!(result.path, vec![7, 8, 9]);
+
+        let result = dijkstra_with_path(&Line, [0], HashMap::new());
+        assert_eq!(result.path, (0..=9).collect::<Vec<_>>());
+    }
+}